@@ -1,11 +1,94 @@
+use anchor_lang::prelude::*;
+
 pub const SEED_PROTOCOL: &[u8] = b"protocol_v1";
 pub const SEED_POOL: &[u8] = b"pool";
 pub const SEED_POOL_VAULT: &[u8] = b"pool_vault";
 pub const SEED_BET: &[u8] = b"bet";
+pub const SEED_PROPOSAL: &[u8] = b"proposal";
+pub const SEED_BOND_VAULT: &[u8] = b"bond_vault";
+pub const SEED_COMMITTEE: &[u8] = b"committee";
+pub const SEED_COMMITTEE_REPORTS: &[u8] = b"committee_reports";
+pub const SEED_CREATOR_BOND: &[u8] = b"creator_bond";
+pub const SEED_REFERRAL: &[u8] = b"referral";
+pub const SEED_REFERRAL_VAULT: &[u8] = b"referral_vault";
+pub const SEED_STAKE_RECEIPT: &[u8] = b"stake_receipt";
+pub const SEED_SOL_VAULT: &[u8] = b"sol_vault";
+pub const SEED_ROLES: &[u8] = b"roles";
+
+// Role bits stored in `Roles.roles`.
+/// Creates pools while paused and without a creation bond; delegates pools to the rollup.
+pub const ROLE_POOL_CREATOR: u8 = 1 << 0;
+/// Resolves pools and commits them back from the rollup.
+pub const ROLE_RESOLVER: u8 = 1 << 1;
+/// Pauses and unpauses the protocol.
+pub const ROLE_PAUSER: u8 = 1 << 2;
+/// Changes fees, bonds and timing in `update_config`; the treasury, arbiter, dispute window,
+/// resolution bond and config delay stay admin-only.
+pub const ROLE_FEE_MANAGER: u8 = 1 << 3;
+/// Pays bet rent as the `sponsor` of `init_bet`.
+pub const ROLE_SPONSOR: u8 = 1 << 4;
+pub const ALL_ROLES: u8 =
+    ROLE_POOL_CREATOR | ROLE_RESOLVER | ROLE_PAUSER | ROLE_FEE_MANAGER | ROLE_SPONSOR;
 
 pub const MAX_STRATEGY_LENGTH: usize = 32;
 pub const MERCY_BUFFER_DEFAULT: u64 = 500;
 pub const DISCRIMINATOR_SIZE: usize = 8;
 
 /// Maximum protocol fee, in basis points (1000 = 10%).
-pub const MAX_FEE_BPS: u64 = 1000;
\ No newline at end of file
+pub const MAX_FEE_BPS: u64 = 1000;
+
+/// Maximum per-pool creator fee, in basis points (500 = 5%).
+pub const MAX_CREATOR_FEE_BPS: u64 = 500;
+
+/// Maximum early-exit penalty on withdraw_bet, in basis points (2000 = 20%).
+pub const MAX_EXIT_PENALTY_BPS: u64 = 2000;
+
+/// Default time, in seconds, winners have to claim once a pool becomes claimable (30 days).
+pub const DEFAULT_CLAIM_WINDOW: i64 = 30 * 24 * 60 * 60;
+
+/// Default shortest claim window a creator may choose (1 day); see `Protocol::min_claim_window`.
+pub const DEFAULT_MIN_CLAIM_WINDOW: i64 = 24 * 60 * 60;
+
+/// Basis-point denominator (10000 = 1x / 100%).
+pub const BPS_DENOMINATOR: u64 = 10000;
+
+/// Maximum extra conviction multiplier for never-revised bets, in basis points (20000 = +2x).
+pub const MAX_CONVICTION_BONUS_BPS: u64 = 20000;
+
+/// Maximum extra early-entry multiplier, in basis points (20000 = +2x).
+pub const MAX_TIME_BONUS_BPS: u64 = 20000;
+
+/// Early-entry bonus hardcoded for pools created before it was configurable: the
+/// quadratic curve with up to +1.5x, given to them by `migrate_pool`.
+pub const LEGACY_TIME_BONUS_MAX_BPS: u64 = 15000;
+
+/// Maximum oracle confidence interval accepted at resolution, in basis points of the price (200 = 2%).
+pub const ORACLE_MAX_CONFIDENCE_BPS: u64 = 200;
+
+/// Pyth (v2) oracle program; price accounts used for resolution must be owned by it.
+pub const PYTH_PROGRAM_ID: Pubkey = pubkey!("FsJ3A3u2vn5cTVofAjvy6y5kwABJAqYWpe4975bi2epH");
+
+/// Default optimistic-resolution dispute window, in seconds.
+pub const DEFAULT_DISPUTE_WINDOW: i64 = 3600;
+
+/// Time the arbiter has to settle a dispute before the pool can be voided and both
+/// bonds refunded (7 days).
+pub const DISPUTE_TIMEOUT: i64 = 7 * 24 * 60 * 60;
+
+/// Config timelock given to protocol accounts migrated from a layout without one (2 days).
+pub const DEFAULT_CONFIG_DELAY: i64 = 2 * 24 * 60 * 60;
+
+/// Maximum number of reporters in a resolver committee.
+pub const MAX_COMMITTEE_SIZE: usize = 10;
+
+/// Miscoverage rate of interval predictions (1000 = 10%, i.e. a 90% band).
+pub const INTERVAL_ALPHA_BPS: u64 = 1000;
+
+/// Outcome limits for categorical pools.
+pub const MAX_OUTCOMES: usize = 8;
+pub const MAX_OUTCOME_LABEL_LENGTH: usize = 32;
+/// Longest pool title, in bytes.
+pub const MAX_TITLE_LENGTH: usize = 128;
+
+/// Largest magnitude accepted for a pool's declared outcome exponent.
+pub const MAX_OUTCOME_EXPONENT: i32 = 18;
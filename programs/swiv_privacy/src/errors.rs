@@ -46,4 +46,108 @@ pub enum CustomError {
     InvalidFee,
     #[msg("New admin must not be the default/zero pubkey.")]
     InvalidAdmin,
+    #[msg("Pool has no oracle feed configured.")]
+    OracleNotConfigured,
+    #[msg("Oracle account does not match the pool feed or is not a valid price account.")]
+    InvalidOracleAccount,
+    #[msg("Oracle price is not currently trading or is non-positive.")]
+    InvalidOraclePrice,
+    #[msg("Oracle price is stale or was published before the pool ended.")]
+    OraclePriceStale,
+    #[msg("Oracle confidence interval is too wide.")]
+    OracleConfidenceTooWide,
+    #[msg("Pool is not awaiting an optimistic resolution step in this state.")]
+    InvalidProposalState,
+    #[msg("The dispute window for this proposal has closed.")]
+    DisputeWindowClosed,
+    #[msg("The dispute window for this proposal is still open.")]
+    DisputeWindowOpen,
+    #[msg("Committee must have 1..=MAX_COMMITTEE_SIZE unique reporters and a threshold within that range.")]
+    InvalidCommittee,
+    #[msg("Signer is not a reporter on this pool's resolver committee.")]
+    NotCommitteeReporter,
+    #[msg("Reporter has already submitted an outcome for this pool.")]
+    DuplicateReport,
+    #[msg("This pool is resolved by its resolver committee.")]
+    CommitteeResolutionRequired,
+    #[msg("Conviction curve parameters are out of range.")]
+    InvalidConvictionCurve,
+    #[msg("Scoring rule parameters are invalid for this pool.")]
+    InvalidScoringRule,
+    #[msg("Time bonus parameters are out of range.")]
+    InvalidTimeBonus,
+    #[msg("Categorical pools need between 2 and 8 non-empty outcome labels of at most 32 bytes.")]
+    InvalidOutcomeLabels,
+    #[msg("Outcome index is out of range for this pool.")]
+    InvalidOutcomeIndex,
+    #[msg("Outcome exponent, scale or resolution source is invalid for this pool kind.")]
+    InvalidOutcomeConfig,
+    #[msg("Pool cannot be voided in its current state, or no reason was given.")]
+    PoolNotVoidable,
+    #[msg("Interval predictions need low <= high and are not allowed in categorical pools.")]
+    InvalidInterval,
+    #[msg("Creator fee exceeds the maximum allowed value.")]
+    InvalidCreatorFee,
+    #[msg("Referral share exceeds 100% of the protocol fee.")]
+    InvalidReferralFee,
+    #[msg("A bet cannot refer itself.")]
+    InvalidReferrer,
+    #[msg("The referrer's ReferralStats and vault must be passed to claim a referred bet.")]
+    ReferralAccountsRequired,
+    #[msg("Nothing to claim.")]
+    NothingToClaim,
+    #[msg("Stake is zero or below the pool's minimum stake.")]
+    StakeBelowMinimum,
+    #[msg("Stake exceeds the pool's maximum stake per bet.")]
+    StakeAboveMaximum,
+    #[msg("Stake would push the pool past its maximum total stake.")]
+    PoolCapacityExceeded,
+    #[msg("Stake limits are inconsistent: min_stake must not exceed the caps.")]
+    InvalidStakeLimits,
+    #[msg("Stake receipt does not belong to this bet.")]
+    StakeReceiptMismatch,
+    #[msg("Exit penalty exceeds the maximum allowed value.")]
+    InvalidExitPenalty,
+    #[msg("Withdrawal amount must be non-zero and at most the bet's stake.")]
+    InvalidWithdrawAmount,
+    #[msg("Claim window must be 0 for the default or at least the protocol minimum.")]
+    InvalidClaimWindow,
+    #[msg("The claim deadline for this pool has passed.")]
+    ClaimDeadlinePassed,
+    #[msg("The claim deadline has not passed yet, or the pool is not claimable.")]
+    ClaimDeadlineNotReached,
+    #[msg("Rollover pool must be open, use the same stake token and differ from the swept pool.")]
+    InvalidRolloverPool,
+    #[msg("Token pools need the pool vault, mint, token accounts and token program; native SOL pools need the SOL vault instead.")]
+    AssetAccountsMismatch,
+    #[msg("This feature is only available for token pools.")]
+    UnsupportedAssetKind,
+    #[msg("Roles must be a non-empty set of known role bits.")]
+    InvalidRoles,
+    #[msg("No admin transfer is pending.")]
+    NoPendingAdmin,
+    #[msg("Account already uses the current layout.")]
+    AccountUpToDate,
+    #[msg("No config change is queued.")]
+    NoPendingConfigChange,
+    #[msg("The queued config change is still timelocked.")]
+    ConfigChangeNotReady,
+    #[msg("Config delay cannot be negative.")]
+    InvalidConfigDelay,
+    #[msg("Pool title is too long.")]
+    TitleTooLong,
+    #[msg("Bet account is missing or not owned by this program or the delegation program.")]
+    BetNotInitialized,
+    #[msg("A config change is already queued; execute or cancel it first.")]
+    ConfigChangePending,
+    #[msg("Stake mint uses a Token-2022 extension the pool vault cannot support.")]
+    UnsupportedMintExtension,
+    #[msg("Optimistic resolution is disabled until the protocol sets a non-zero resolution bond.")]
+    ResolutionBondNotSet,
+    #[msg("The arbiter can still settle this dispute.")]
+    DisputeTimeoutNotReached,
+    #[msg("A timed-out dispute needs its proposal, bond vault, proposer and both bond token accounts.")]
+    DisputeAccountsMissing,
+    #[msg("The bet's pool is still open; claim or refund through it instead.")]
+    PoolStillOpen,
 }
\ No newline at end of file
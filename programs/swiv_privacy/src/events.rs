@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::state::VoidReason;
 
 #[event]
 pub struct ProtocolInitialized {
@@ -11,6 +12,13 @@ pub struct ConfigUpdated {
     pub treasury: Option<Pubkey>,
     pub protocol_fee_bps: Option<u64>,
     pub batch_settle_wait_duration: Option<i64>,
+    pub dispute_window: Option<i64>,
+    pub resolution_bond: Option<u64>,
+    pub arbiter: Option<Pubkey>,
+    pub creation_bond: Option<u64>,
+    pub referral_fee_bps: Option<u64>,
+    pub config_delay: Option<i64>,
+    pub min_claim_window: Option<i64>,
 }
 
 #[event]
@@ -18,6 +26,8 @@ pub struct PoolCreated {
     pub pool_name: String,
     pub start_time: i64,
     pub end_time: i64,
+    pub subsidy_amount: u64,
+    pub fee_bps: u64,
 }
 
 #[event]
@@ -33,4 +43,241 @@ pub struct BetRefunded {
     pub user: Pubkey,
     pub amount: u64,
     pub is_emergency: bool,
-}
\ No newline at end of file
+}
+
+#[event]
+pub struct PoolResolvedFromOracle {
+    pub pool: Pubkey,
+    pub outcome: u64,
+    pub oracle_price: i64,
+    pub oracle_conf: u64,
+    pub oracle_expo: i32,
+    pub publish_time: i64,
+}
+
+#[event]
+pub struct OutcomeProposed {
+    pub pool: Pubkey,
+    pub proposer: Pubkey,
+    pub proposed_outcome: u64,
+    pub bond_amount: u64,
+    pub dispute_deadline: i64,
+}
+
+#[event]
+pub struct OutcomeDisputed {
+    pub pool: Pubkey,
+    pub disputer: Pubkey,
+    pub bond_amount: u64,
+}
+
+#[event]
+pub struct ProposalSettled {
+    pub pool: Pubkey,
+    pub proposer: Pubkey,
+    pub outcome: u64,
+}
+
+#[event]
+pub struct DisputeArbitrated {
+    pub pool: Pubkey,
+    pub arbiter: Pubkey,
+    pub final_outcome: u64,
+    pub proposer_won: bool,
+    pub slashed_amount: u64,
+}
+
+#[event]
+pub struct DisputeBondsRefunded {
+    pub pool: Pubkey,
+    pub proposer_amount: u64,
+    pub disputer_amount: u64,
+}
+
+#[event]
+pub struct CommitteeCreated {
+    pub committee: Pubkey,
+    pub reporters: Vec<Pubkey>,
+    pub threshold: u8,
+}
+
+#[event]
+pub struct ReportSubmitted {
+    pub pool: Pubkey,
+    pub reporter: Pubkey,
+    pub outcome: u64,
+}
+
+#[event]
+pub struct CommitteeResolved {
+    pub pool: Pubkey,
+    pub committee: Pubkey,
+    pub median_outcome: u64,
+    pub report_count: u8,
+}
+
+#[event]
+pub struct OutlierReportFlagged {
+    pub pool: Pubkey,
+    pub reporter: Pubkey,
+    pub outcome: u64,
+    pub median_outcome: u64,
+    pub deviation_bps: u64,
+}
+
+#[event]
+pub struct PoolVoided {
+    pub pool: Pubkey,
+    pub reason: VoidReason,
+    pub total_staked: u64,
+    pub voided_at: i64,
+}
+
+#[event]
+pub struct CreatorBondPosted {
+    pub pool: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct CreatorBondReleased {
+    pub pool: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct CreatorBondSlashed {
+    pub pool: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct PoolFinalized {
+    pub pool: Pubkey,
+    pub total_assets: u64,
+    pub protocol_fee: u64,
+    pub creator_fee: u64,
+    /// Part of the protocol fee held back in the vault for referrers.
+    pub referral_reserve: u64,
+    /// Subsidy included in `distributable_amount` (0 if it was returned to the creator).
+    pub subsidy_distributed: u64,
+    pub distributable_amount: u64,
+}
+
+#[event]
+pub struct ReferralFeeAccrued {
+    pub referrer: Pubkey,
+    pub pool: Pubkey,
+    pub bet: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ReferralFeesClaimed {
+    pub referrer: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct SubsidyReturned {
+    pub pool: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct StakeAdded {
+    pub bet: Pubkey,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub nonce: u64,
+    pub total_amount: u64,
+}
+
+#[event]
+pub struct BetWithdrawn {
+    pub bet: Pubkey,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub penalty: u64,
+    pub remaining_stake: u64,
+    pub closed: bool,
+}
+
+#[event]
+pub struct UnclaimedSwept {
+    pub pool: Pubkey,
+    pub amount: u64,
+    /// Treasury token account, or the rollover pool when `rolled_over` is set.
+    pub destination: Pubkey,
+    pub rolled_over: bool,
+    /// Bets that never claimed before the deadline.
+    pub unclaimed_bets: u64,
+    pub swept_at: i64,
+}
+
+#[event]
+pub struct OrphanedBetClosed {
+    pub bet: Pubkey,
+    pub pool: Pubkey,
+    pub user: Pubkey,
+    pub sponsor: Pubkey,
+}
+
+#[event]
+pub struct RoleGranted {
+    pub holder: Pubkey,
+    pub granted: u8,
+    pub roles: u8,
+}
+
+#[event]
+pub struct RoleRevoked {
+    pub holder: Pubkey,
+    pub revoked: u8,
+    pub roles: u8,
+}
+
+#[event]
+pub struct AdminTransferProposed {
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,
+}
+
+#[event]
+pub struct AdminTransferAccepted {
+    pub previous_admin: Pubkey,
+    pub new_admin: Pubkey,
+}
+
+#[event]
+pub struct AdminTransferCancelled {
+    pub admin: Pubkey,
+    pub cancelled: Pubkey,
+}
+
+#[event]
+pub struct AccountMigrated {
+    pub account: Pubkey,
+    pub old_len: u64,
+    pub new_len: u64,
+    pub version: u8,
+}
+
+#[event]
+pub struct ConfigChangeQueued {
+    pub treasury: Option<Pubkey>,
+    pub protocol_fee_bps: Option<u64>,
+    pub batch_settle_wait_duration: Option<i64>,
+    pub config_delay: Option<i64>,
+    pub effective_at: i64,
+}
+
+#[event]
+pub struct ConfigChangeCancelled {
+    pub effective_at: i64,
+}
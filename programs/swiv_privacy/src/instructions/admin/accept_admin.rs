@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::state::Protocol;
+use crate::constants::SEED_PROTOCOL;
+use crate::errors::CustomError;
+use crate::events::AdminTransferAccepted;
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    pub pending_admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_PROTOCOL],
+        bump,
+        constraint = protocol.pending_admin != Pubkey::default() @ CustomError::NoPendingAdmin,
+        constraint = protocol.pending_admin == pending_admin.key() @ CustomError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+}
+
+pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+    let protocol = &mut ctx.accounts.protocol;
+    let previous_admin = protocol.admin;
+
+    protocol.admin = protocol.pending_admin;
+    protocol.pending_admin = Pubkey::default();
+
+    emit!(AdminTransferAccepted {
+        previous_admin,
+        new_admin: protocol.admin,
+    });
+
+    Ok(())
+}
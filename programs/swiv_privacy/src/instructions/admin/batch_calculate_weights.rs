@@ -1,8 +1,9 @@
 use crate::constants::SEED_POOL;
 use crate::errors::CustomError;
-use crate::state::{BetStatus, Pool, PoolStatus, Bet};
+use crate::state::{BetStatus, Pool, PoolKind, PoolStatus, Bet, ScoringRule};
 use crate::utils::math::{
-    calculate_accuracy_score, calculate_conviction_bonus, calculate_time_bonus, calculate_weight,
+    calculate_categorical_score, calculate_conviction_bonus, calculate_error_score,
+    calculate_interval_error, calculate_time_bonus, calculate_weight,
 };
 use anchor_lang::prelude::*;
 
@@ -45,31 +46,78 @@ pub fn batch_calculate_weights<'info>(
             continue; 
         }
 
-        let accuracy_score = calculate_accuracy_score(
-            bet.prediction,
-            result,
-            pool.max_accuracy_buffer,
-        )?;
+        let (error, accuracy_score) = match pool.kind {
+            PoolKind::Numeric | PoolKind::SignedNumeric => {
+                let error = calculate_interval_error(
+                    pool.outcome_value(bet.prediction),
+                    pool.outcome_value(bet.prediction_high),
+                    pool.outcome_value(result),
+                )?;
+                let score = calculate_error_score(
+                    pool.scoring_rule,
+                    error,
+                    pool.accuracy_reference(result),
+                    pool.max_accuracy_buffer,
+                )?;
+                (error, score)
+            }
+            PoolKind::Categorical => {
+                // update_count == 0 means place_bet was never called, so the bet has no pick.
+                let has_pick = bet.update_count > 0;
+                if has_pick {
+                    let index = bet.prediction as usize;
+                    if let Some(total) = pool.outcome_totals.get_mut(index) {
+                        *total = total.checked_add(bet.stake).ok_or(CustomError::MathOverflow)?;
+                    }
+                }
+                let is_winner = has_pick && bet.prediction == result;
+                (u64::from(!is_winner), calculate_categorical_score(is_winner))
+            }
+        };
         
         let time_bonus = calculate_time_bonus(
+            pool.time_bonus_curve,
+            pool.time_bonus_max_bps,
             start_time, 
             cutoff_time, 
-            bet.creation_ts
+            bet.bonus_entry_ts.max(bet.creation_ts)
         )?;
         
-        let conviction_bonus = calculate_conviction_bonus(bet.update_count);
+        let conviction_bonus = calculate_conviction_bonus(
+            bet.update_count,
+            pool.conviction_bonus_bps,
+            pool.conviction_decay_bps,
+            pool.conviction_floor_bps,
+        )?;
         
-        let weight = calculate_weight(
+        let mut weight = calculate_weight(
             bet.stake,
             accuracy_score,
             time_bonus,
             conviction_bonus,
         )?;
 
+        // Winner-take-closest: a strictly closer bet resets the pot's weight. Bets
+        // scored earlier keep their weight but are excluded at claim time.
+        if pool.kind != PoolKind::Categorical
+            && pool.scoring_rule == ScoringRule::WinnerTakeClosest
+            && weight > 0
+        {
+            if error < pool.closest_error {
+                pool.closest_error = error;
+                pool.total_weight = 0;
+            } else if error > pool.closest_error {
+                weight = 0;
+            }
+        }
+
         pool.total_weight = pool.total_weight.checked_add(weight).unwrap();
         pool.weights_calculated_count = pool.weights_calculated_count.checked_add(1).unwrap();
+        // What the rollup credited is now stake; the rest stays pending for refund at claim.
+        pool.pending_top_ups = pool.pending_top_ups.saturating_sub(bet.credited_top_ups);
 
         bet.calculated_weight = weight;
+        bet.scored_error = error;
         bet.is_weight_added = true;
         bet.status = BetStatus::Resolved;
 
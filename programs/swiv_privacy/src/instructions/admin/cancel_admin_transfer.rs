@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+use crate::state::Protocol;
+use crate::constants::SEED_PROTOCOL;
+use crate::errors::CustomError;
+use crate::events::AdminTransferCancelled;
+
+#[derive(Accounts)]
+pub struct CancelAdminTransfer<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_PROTOCOL],
+        bump,
+        constraint = protocol.admin == admin.key() @ CustomError::Unauthorized,
+        constraint = protocol.pending_admin != Pubkey::default() @ CustomError::NoPendingAdmin
+    )]
+    pub protocol: Account<'info, Protocol>,
+}
+
+pub fn cancel_admin_transfer(ctx: Context<CancelAdminTransfer>) -> Result<()> {
+    let protocol = &mut ctx.accounts.protocol;
+    let cancelled = protocol.pending_admin;
+
+    protocol.pending_admin = Pubkey::default();
+
+    emit!(AdminTransferCancelled {
+        admin: protocol.admin,
+        cancelled,
+    });
+
+    Ok(())
+}
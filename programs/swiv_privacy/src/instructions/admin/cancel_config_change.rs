@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+use crate::state::Protocol;
+use crate::constants::SEED_PROTOCOL;
+use crate::errors::CustomError;
+use crate::events::ConfigChangeCancelled;
+
+#[derive(Accounts)]
+pub struct CancelConfigChange<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_PROTOCOL],
+        bump,
+        constraint = protocol.admin == admin.key() @ CustomError::Unauthorized,
+        constraint = !protocol.pending_config.is_empty() @ CustomError::NoPendingConfigChange
+    )]
+    pub protocol: Account<'info, Protocol>,
+}
+
+pub fn cancel_config_change(ctx: Context<CancelConfigChange>) -> Result<()> {
+    let protocol = &mut ctx.accounts.protocol;
+    let cancelled = std::mem::take(&mut protocol.pending_config);
+
+    emit!(ConfigChangeCancelled {
+        effective_at: cancelled.effective_at,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use crate::state::{Protocol, ResolverCommittee};
+use crate::constants::{SEED_COMMITTEE, SEED_PROTOCOL, MAX_COMMITTEE_SIZE};
+use crate::errors::CustomError;
+use crate::events::CommitteeCreated;
+
+#[derive(Accounts)]
+#[instruction(committee_id: u64)]
+pub struct CreateCommittee<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_PROTOCOL],
+        bump,
+        constraint = protocol.admin == admin.key() @ CustomError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = ResolverCommittee::SPACE,
+        seeds = [SEED_COMMITTEE, &committee_id.to_le_bytes()],
+        bump
+    )]
+    pub committee: Account<'info, ResolverCommittee>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_committee(
+    ctx: Context<CreateCommittee>,
+    committee_id: u64,
+    reporters: Vec<Pubkey>,
+    threshold: u8,
+    outlier_threshold_bps: u64,
+) -> Result<()> {
+    require!(
+        !reporters.is_empty() && reporters.len() <= MAX_COMMITTEE_SIZE,
+        CustomError::InvalidCommittee
+    );
+    require!(
+        threshold > 0 && threshold as usize <= reporters.len(),
+        CustomError::InvalidCommittee
+    );
+
+    for (i, reporter) in reporters.iter().enumerate() {
+        require!(*reporter != Pubkey::default(), CustomError::InvalidCommittee);
+        require!(!reporters[..i].contains(reporter), CustomError::InvalidCommittee);
+    }
+
+    let committee = &mut ctx.accounts.committee;
+    committee.committee_id = committee_id;
+    committee.reporters = reporters.clone();
+    committee.threshold = threshold;
+    committee.outlier_threshold_bps = outlier_threshold_bps;
+    committee.bump = ctx.bumps.committee;
+
+    emit!(CommitteeCreated {
+        committee: committee.key(),
+        reporters,
+        threshold,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+use crate::state::{PendingConfig, Protocol};
+use crate::constants::SEED_PROTOCOL;
+use crate::errors::CustomError;
+use crate::events::ConfigUpdated;
+
+#[derive(Accounts)]
+pub struct ExecuteConfigChange<'info> {
+    /// Permissionless: the change was authorized when it was queued.
+    pub executor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_PROTOCOL],
+        bump,
+        constraint = !protocol.pending_config.is_empty() @ CustomError::NoPendingConfigChange
+    )]
+    pub protocol: Account<'info, Protocol>,
+}
+
+pub fn execute_config_change(ctx: Context<ExecuteConfigChange>) -> Result<()> {
+    let protocol = &mut ctx.accounts.protocol;
+    let change = std::mem::take(&mut protocol.pending_config);
+
+    require!(
+        Clock::get()?.unix_timestamp >= change.effective_at,
+        CustomError::ConfigChangeNotReady
+    );
+
+    if let Some(treasury) = change.treasury {
+        protocol.treasury_wallet = treasury;
+    }
+
+    if let Some(fee) = change.protocol_fee_bps {
+        protocol.protocol_fee_bps = fee;
+    }
+
+    if let Some(duration) = change.batch_settle_wait_duration {
+        protocol.batch_settle_wait_duration = duration;
+    }
+
+    if let Some(delay) = change.config_delay {
+        protocol.config_delay = delay;
+    }
+
+    let PendingConfig { treasury, protocol_fee_bps, batch_settle_wait_duration, config_delay, .. } = change;
+    emit!(ConfigUpdated {
+        treasury,
+        protocol_fee_bps,
+        batch_settle_wait_duration,
+        dispute_window: None,
+        resolution_bond: None,
+        arbiter: None,
+        creation_bond: None,
+        referral_fee_bps: None,
+        config_delay,
+        min_claim_window: None,
+    });
+
+    Ok(())
+}
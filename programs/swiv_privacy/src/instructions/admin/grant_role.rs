@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use crate::state::{Protocol, Roles};
+use crate::constants::{ALL_ROLES, SEED_PROTOCOL, SEED_ROLES};
+use crate::errors::CustomError;
+use crate::events::RoleGranted;
+
+#[derive(Accounts)]
+#[instruction(holder: Pubkey)]
+pub struct GrantRole<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_PROTOCOL],
+        bump,
+        constraint = protocol.admin == admin.key() @ CustomError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = Roles::SPACE,
+        seeds = [SEED_ROLES, holder.as_ref()],
+        bump
+    )]
+    pub roles: Account<'info, Roles>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn grant_role(ctx: Context<GrantRole>, holder: Pubkey, roles: u8) -> Result<()> {
+    require!(roles != 0 && roles & !ALL_ROLES == 0, CustomError::InvalidRoles);
+    require!(holder != Pubkey::default(), CustomError::InvalidAdmin);
+
+    let account = &mut ctx.accounts.roles;
+    account.holder = holder;
+    account.roles |= roles;
+    account.bump = ctx.bumps.roles;
+
+    emit!(RoleGranted {
+        holder,
+        granted: roles,
+        roles: account.roles,
+    });
+
+    Ok(())
+}
@@ -1,18 +1,19 @@
 use anchor_lang::prelude::*;
-use crate::state::Protocol;
-use crate::constants::{SEED_PROTOCOL, MAX_FEE_BPS};
+use crate::state::{PendingConfig, Protocol};
+use crate::constants::{SEED_PROTOCOL, MAX_FEE_BPS, DEFAULT_DISPUTE_WINDOW, DEFAULT_MIN_CLAIM_WINDOW};
 use crate::errors::CustomError;
 use crate::events::ProtocolInitialized;
 
 #[derive(Accounts)]
 #[instruction(
-    protocol_fee_bps: u64 
+    protocol_fee_bps: u64,
+    config_delay: i64
 )]
 pub struct InitializeProtocol<'info> {
     #[account(
         init,
         payer = admin,
-        space = Protocol::BASE_LEN,
+        space = Protocol::SPACE,
         seeds = [SEED_PROTOCOL],
         bump
     )]
@@ -29,9 +30,11 @@ pub struct InitializeProtocol<'info> {
 
 pub fn initialize_protocol(
     ctx: Context<InitializeProtocol>,
-    protocol_fee_bps: u64 
+    protocol_fee_bps: u64,
+    config_delay: i64
 ) -> Result<()> {
     require!(protocol_fee_bps <= MAX_FEE_BPS, CustomError::InvalidFee);
+    require!(config_delay >= 0, CustomError::InvalidConfigDelay);
 
     let protocol = &mut ctx.accounts.protocol;
 
@@ -43,6 +46,16 @@ pub fn initialize_protocol(
     protocol.paused = false;
     protocol.total_pools = 0;
     protocol.batch_settle_wait_duration = 60; 
+    protocol.dispute_window = DEFAULT_DISPUTE_WINDOW;
+    protocol.resolution_bond = 0;
+    protocol.arbiter = ctx.accounts.admin.key();
+    protocol.creation_bond = 0;
+    protocol.referral_fee_bps = 0;
+    protocol.pending_admin = Pubkey::default();
+    protocol.config_delay = config_delay;
+    protocol.pending_config = PendingConfig::default();
+    protocol.version = Protocol::VERSION;
+    protocol.min_claim_window = DEFAULT_MIN_CLAIM_WINDOW;
 
     emit!(ProtocolInitialized {
         admin: ctx.accounts.admin.key(),
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use crate::state::Protocol;
+use crate::constants::SEED_PROTOCOL;
+use crate::errors::CustomError;
+use crate::events::AccountMigrated;
+use crate::utils::{grow_account, upgrade_account};
+
+#[derive(Accounts)]
+pub struct MigrateProtocol<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// CHECK: Deserialized by hand because an account on an older layout may be too
+    /// short for `Account<Protocol>`.
+    #[account(mut, seeds = [SEED_PROTOCOL], bump, owner = crate::ID)]
+    pub protocol: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Reallocs a `protocol_v1` account written under an older layout to `Protocol::SPACE`
+/// and upgrades it to `Protocol::VERSION`.
+pub fn migrate_protocol(ctx: Context<MigrateProtocol>) -> Result<()> {
+    let info = ctx.accounts.protocol.to_account_info();
+    let old_len = info.data_len();
+
+    {
+        let data = info.try_borrow_data()?;
+        require!(
+            old_len >= 8 + 32 && data[..8] == *Protocol::DISCRIMINATOR,
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        let admin = Pubkey::try_from(&data[8..40]).unwrap();
+        require_keys_eq!(admin, ctx.accounts.admin.key(), CustomError::Unauthorized);
+    }
+
+    grow_account(
+        &info,
+        &ctx.accounts.admin.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        Protocol::SPACE,
+    )?;
+    upgrade_account::<Protocol>(&mut info.try_borrow_mut_data()?, Protocol::upgrade)?;
+
+    emit!(AccountMigrated {
+        account: info.key(),
+        old_len: old_len as u64,
+        new_len: info.data_len() as u64,
+        version: Protocol::VERSION,
+    });
+
+    Ok(())
+}
@@ -2,10 +2,28 @@ pub mod initialize_protocol;
 pub mod set_pause;
 pub mod batch_calculate_weights;
 pub mod update_config;
-pub mod transfer_admin;
+pub mod propose_admin;
+pub mod accept_admin;
+pub mod cancel_admin_transfer;
+pub mod migrate_protocol;
+pub mod execute_config_change;
+pub mod cancel_config_change;
+pub mod create_committee;
+pub mod moderate_creator_bond;
+pub mod grant_role;
+pub mod revoke_role;
 
 pub use initialize_protocol::*;
 pub use set_pause::*;
 pub use batch_calculate_weights::*;
 pub use update_config::*;
-pub use transfer_admin::*;
\ No newline at end of file
+pub use propose_admin::*;
+pub use accept_admin::*;
+pub use cancel_admin_transfer::*;
+pub use migrate_protocol::*;
+pub use execute_config_change::*;
+pub use cancel_config_change::*;
+pub use create_committee::*;
+pub use moderate_creator_bond::*;
+pub use grant_role::*;
+pub use revoke_role::*;
\ No newline at end of file
@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+use crate::state::{BondModeration, CreatorBond, Protocol};
+use crate::constants::{SEED_CREATOR_BOND, SEED_PROTOCOL};
+use crate::errors::CustomError;
+use crate::events::{CreatorBondReleased, CreatorBondSlashed};
+
+#[derive(Accounts)]
+pub struct ModerateCreatorBond<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_PROTOCOL],
+        bump,
+        constraint = protocol.admin == admin.key() @ CustomError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+
+    /// The pool itself is not required, so bonds can still be settled after the
+    /// pool account has been closed.
+    #[account(
+        mut,
+        close = creator,
+        seeds = [SEED_CREATOR_BOND, creator_bond.pool.as_ref()],
+        bump = creator_bond.bump,
+    )]
+    pub creator_bond: Account<'info, CreatorBond>,
+
+    /// CHECK: Receives the account rent (and the bond on release); validated against the bond record.
+    #[account(mut, address = creator_bond.creator @ CustomError::Unauthorized)]
+    pub creator: UncheckedAccount<'info>,
+
+    /// CHECK: Receives slashed bonds; validated against the protocol treasury.
+    #[account(mut, address = protocol.treasury_wallet @ CustomError::Unauthorized)]
+    pub treasury: UncheckedAccount<'info>,
+}
+
+pub fn moderate_creator_bond(
+    ctx: Context<ModerateCreatorBond>,
+    action: BondModeration,
+) -> Result<()> {
+    let bond = &ctx.accounts.creator_bond;
+    let amount = bond.amount;
+
+    match action {
+        BondModeration::Release => {
+            emit!(CreatorBondReleased {
+                pool: bond.pool,
+                creator: bond.creator,
+                amount,
+            });
+        }
+        BondModeration::Slash => {
+            if amount > 0 {
+                let bond_info = ctx.accounts.creator_bond.to_account_info();
+                let treasury_info = ctx.accounts.treasury.to_account_info();
+
+                **bond_info.try_borrow_mut_lamports()? = bond_info
+                    .lamports()
+                    .checked_sub(amount)
+                    .ok_or(CustomError::MathOverflow)?;
+                **treasury_info.try_borrow_mut_lamports()? = treasury_info
+                    .lamports()
+                    .checked_add(amount)
+                    .ok_or(CustomError::MathOverflow)?;
+            }
+
+            emit!(CreatorBondSlashed {
+                pool: bond.pool,
+                creator: bond.creator,
+                amount,
+            });
+        }
+    }
+
+    // Remaining lamports (rent, plus the bond on release) go to the creator via `close`.
+    Ok(())
+}
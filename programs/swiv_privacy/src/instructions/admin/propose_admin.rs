@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::state::Protocol;
+use crate::constants::SEED_PROTOCOL;
+use crate::errors::CustomError;
+use crate::events::AdminTransferProposed;
+
+#[derive(Accounts)]
+pub struct ProposeAdmin<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_PROTOCOL],
+        bump,
+        constraint = protocol.admin == admin.key() @ CustomError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+}
+
+/// First step of an admin handover. `new_admin` takes over only once it signs
+/// `accept_admin`; proposing again replaces the pending key.
+pub fn propose_admin(ctx: Context<ProposeAdmin>, new_admin: Pubkey) -> Result<()> {
+    require!(
+        new_admin != Pubkey::default() && new_admin != ctx.accounts.protocol.admin,
+        CustomError::InvalidAdmin
+    );
+
+    let protocol = &mut ctx.accounts.protocol;
+    protocol.pending_admin = new_admin;
+
+    emit!(AdminTransferProposed {
+        admin: protocol.admin,
+        pending_admin: new_admin,
+    });
+
+    Ok(())
+}
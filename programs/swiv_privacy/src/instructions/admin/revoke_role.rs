@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+use crate::state::{Protocol, Roles};
+use crate::constants::{ALL_ROLES, SEED_PROTOCOL, SEED_ROLES};
+use crate::errors::CustomError;
+use crate::events::RoleRevoked;
+
+#[derive(Accounts)]
+pub struct RevokeRole<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_PROTOCOL],
+        bump,
+        constraint = protocol.admin == admin.key() @ CustomError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+
+    #[account(
+        mut,
+        seeds = [SEED_ROLES, roles.holder.as_ref()],
+        bump = roles.bump
+    )]
+    pub roles: Account<'info, Roles>,
+}
+
+/// Clears `roles` from the holder; the account is closed to the admin once no role remains.
+pub fn revoke_role(ctx: Context<RevokeRole>, roles: u8) -> Result<()> {
+    require!(roles != 0 && roles & !ALL_ROLES == 0, CustomError::InvalidRoles);
+
+    let account = &mut ctx.accounts.roles;
+    account.roles &= !roles;
+
+    emit!(RoleRevoked {
+        holder: account.holder,
+        revoked: roles,
+        roles: account.roles,
+    });
+
+    if account.roles == 0 {
+        account.close(ctx.accounts.admin.to_account_info())?;
+    }
+
+    Ok(())
+}
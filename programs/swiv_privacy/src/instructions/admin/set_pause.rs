@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
-use crate::state::Protocol;
-use crate::constants::SEED_PROTOCOL;
+use crate::state::{Protocol, Roles};
+use crate::constants::{ROLE_PAUSER, SEED_PROTOCOL, SEED_ROLES};
 use crate::errors::CustomError;
 
 #[derive(Accounts)]
@@ -9,11 +9,15 @@ pub struct SetPause<'info> {
         mut,
         seeds = [SEED_PROTOCOL],
         bump,
-        constraint = protocol.admin == admin.key() @ CustomError::Unauthorized
+        constraint = protocol.has_role(admin.key(), roles.as_deref(), ROLE_PAUSER) @ CustomError::Unauthorized
     )]
     pub protocol: Account<'info, Protocol>,
 
     pub admin: Signer<'info>,
+
+    /// Roles PDA of `admin`; omit when `admin` is the protocol admin.
+    #[account(seeds = [SEED_ROLES, admin.key().as_ref()], bump = roles.bump)]
+    pub roles: Option<Account<'info, Roles>>,
 }
 
 pub fn set_pause(ctx: Context<SetPause>, paused: bool) -> Result<()> {
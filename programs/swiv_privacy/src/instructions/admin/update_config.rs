@@ -1,14 +1,21 @@
 use anchor_lang::prelude::*;
-use crate::state::Protocol;
-use crate::constants::{SEED_PROTOCOL, MAX_FEE_BPS};
+use crate::state::{PendingConfig, Protocol, Roles};
+use crate::constants::{BPS_DENOMINATOR, MAX_FEE_BPS, ROLE_FEE_MANAGER, SEED_PROTOCOL, SEED_ROLES};
 use crate::errors::CustomError;
-use crate::events::ConfigUpdated;
+use crate::events::{ConfigChangeQueued, ConfigUpdated};
 
 #[derive(Accounts)]
 #[instruction(
     new_treasury: Option<Pubkey>,
     new_protocol_fee_bps: Option<u64>,
     new_batch_settle_wait_duration: Option<i64>,
+    new_dispute_window: Option<i64>,
+    new_resolution_bond: Option<u64>,
+    new_arbiter: Option<Pubkey>,
+    new_creation_bond: Option<u64>,
+    new_referral_fee_bps: Option<u64>,
+    new_config_delay: Option<i64>,
+    new_min_claim_window: Option<i64>,
 )]
 pub struct UpdateConfig<'info> {
     #[account(mut)]
@@ -18,38 +25,129 @@ pub struct UpdateConfig<'info> {
         mut,
         seeds = [SEED_PROTOCOL],
         bump,
-        constraint = protocol.admin == admin.key() @ CustomError::Unauthorized,
+        constraint = protocol.has_role(admin.key(), roles.as_deref(), ROLE_FEE_MANAGER) @ CustomError::Unauthorized,
     )]
     pub protocol: Account<'info, Protocol>,
 
+    /// Roles PDA of `admin`; omit when `admin` is the protocol admin.
+    #[account(seeds = [SEED_ROLES, admin.key().as_ref()], bump = roles.bump)]
+    pub roles: Option<Account<'info, Roles>>,
+
     pub system_program: Program<'info, System>,
 }
 
+/// Bonds, the dispute window, the arbiter, the referral share and the minimum claim
+/// window apply at once. The
+/// treasury, protocol fee, settle wait and the delay itself are queued for
+/// `execute_config_change` after `protocol.config_delay`. Only one change can be
+/// queued at a time, so it can only be replaced through the admin's
+/// `cancel_config_change`. A queued fee never reaches existing pools: each one
+/// settles with the `fee_bps` it snapshotted at `create_pool`.
+#[allow(clippy::too_many_arguments)]
 pub fn update_config(
     ctx: Context<UpdateConfig>,
     new_treasury: Option<Pubkey>,
     new_protocol_fee_bps: Option<u64>,
     new_batch_settle_wait_duration: Option<i64>,
+    new_dispute_window: Option<i64>,
+    new_resolution_bond: Option<u64>,
+    new_arbiter: Option<Pubkey>,
+    new_creation_bond: Option<u64>,
+    new_referral_fee_bps: Option<u64>,
+    new_config_delay: Option<i64>,
+    new_min_claim_window: Option<i64>,
 ) -> Result<()> {
     let protocol = &mut ctx.accounts.protocol;
 
-    if let Some(treasury) = new_treasury {
-        protocol.treasury_wallet = treasury;
+    // Fee managers tune economics; redirecting funds, anything that decides who resolves
+    // a pool (a tiny bond or window lets a proposer settle unchallenged), how long winners
+    // have to claim and the timelock stay with the admin.
+    if new_treasury.is_some()
+        || new_arbiter.is_some()
+        || new_dispute_window.is_some()
+        || new_resolution_bond.is_some()
+        || new_config_delay.is_some()
+        || new_min_claim_window.is_some()
+    {
+        require_keys_eq!(ctx.accounts.admin.key(), protocol.admin, CustomError::Unauthorized);
     }
 
     if let Some(fee) = new_protocol_fee_bps {
         require!(fee <= MAX_FEE_BPS, CustomError::InvalidFee);
-        protocol.protocol_fee_bps = fee;
     }
 
-    if let Some(duration) = new_batch_settle_wait_duration {
-        protocol.batch_settle_wait_duration = duration;
+    if let Some(delay) = new_config_delay {
+        require!(delay >= 0, CustomError::InvalidConfigDelay);
     }
 
-    emit!(ConfigUpdated {
+    let queued = PendingConfig {
         treasury: new_treasury,
         protocol_fee_bps: new_protocol_fee_bps,
         batch_settle_wait_duration: new_batch_settle_wait_duration,
+        config_delay: new_config_delay,
+        effective_at: 0,
+    };
+
+    if !queued.is_empty() {
+        // Replacing the pending change would restart its clock, or let a fee manager
+        // overwrite an admin-only treasury or delay change.
+        require!(protocol.pending_config.is_empty(), CustomError::ConfigChangePending);
+
+        let effective_at = Clock::get()?
+            .unix_timestamp
+            .checked_add(protocol.config_delay)
+            .ok_or(CustomError::MathOverflow)?;
+
+        protocol.pending_config = PendingConfig { effective_at, ..queued };
+
+        emit!(ConfigChangeQueued {
+            treasury: new_treasury,
+            protocol_fee_bps: new_protocol_fee_bps,
+            batch_settle_wait_duration: new_batch_settle_wait_duration,
+            config_delay: new_config_delay,
+            effective_at,
+        });
+    }
+
+    if let Some(window) = new_dispute_window {
+        require!(window > 0, CustomError::DurationTooShort);
+        protocol.dispute_window = window;
+    }
+
+    if let Some(bond) = new_resolution_bond {
+        protocol.resolution_bond = bond;
+    }
+
+    if let Some(arbiter) = new_arbiter {
+        require!(arbiter != Pubkey::default(), CustomError::InvalidAdmin);
+        protocol.arbiter = arbiter;
+    }
+
+    if let Some(bond) = new_creation_bond {
+        protocol.creation_bond = bond;
+    }
+
+    if let Some(share) = new_referral_fee_bps {
+        require!(share <= BPS_DENOMINATOR, CustomError::InvalidReferralFee);
+        protocol.referral_fee_bps = share;
+    }
+
+    if let Some(window) = new_min_claim_window {
+        require!(window >= 0, CustomError::InvalidClaimWindow);
+        protocol.min_claim_window = window;
+    }
+
+    emit!(ConfigUpdated {
+        treasury: None,
+        protocol_fee_bps: None,
+        batch_settle_wait_duration: None,
+        dispute_window: new_dispute_window,
+        resolution_bond: new_resolution_bond,
+        arbiter: new_arbiter,
+        creation_bond: new_creation_bond,
+        referral_fee_bps: new_referral_fee_bps,
+        config_delay: None,
+        min_claim_window: new_min_claim_window,
     });
 
     msg!("Protocol Config Updated");
@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
-use crate::state::{Bet, Protocol, Pool}; 
-use crate::constants::{SEED_BET, SEED_POOL, SEED_PROTOCOL}; 
+use crate::state::{Bet, Protocol, Pool, Roles}; 
+use crate::constants::{ROLE_POOL_CREATOR, ROLE_RESOLVER, SEED_BET, SEED_POOL, SEED_PROTOCOL, SEED_ROLES}; 
 use crate::errors::CustomError;
 
 use ephemeral_rollups_sdk::anchor::{delegate, commit};
@@ -17,15 +17,22 @@ pub struct DelegatePool<'info> {
     #[account(
         seeds = [SEED_PROTOCOL],
         bump,
-        constraint = protocol.admin == admin.key() @ CustomError::Unauthorized
+        constraint = protocol.has_role(admin.key(), roles.as_deref(), ROLE_POOL_CREATOR) @ CustomError::Unauthorized
     )]
     pub protocol: Account<'info, Protocol>,
 
+    /// Roles PDA of `admin`; omit when `admin` is the protocol admin.
+    #[account(seeds = [SEED_ROLES, admin.key().as_ref()], bump = roles.bump)]
+    pub roles: Option<Account<'info, Roles>>,
+
+    /// CHECK: Only used to derive the pool PDA; pools may be created by any wallet.
+    pub pool_creator: UncheckedAccount<'info>,
+
     /// CHECK: The main pool account.
    #[account(
         mut, 
         del, 
-        seeds = [SEED_POOL, admin.key().as_ref(), &pool_id.to_le_bytes()],
+        seeds = [SEED_POOL, pool_creator.key().as_ref(), &pool_id.to_le_bytes()],
         bump
     )]
     pub pool: UncheckedAccount<'info>,
@@ -35,12 +42,12 @@ pub struct DelegatePool<'info> {
 }
 
 pub fn delegate_pool<'info>(ctx: Context<'info, DelegatePool<'info>>, pool_id: u64) -> Result<()> {
-    let admin_key = ctx.accounts.admin.key();
-    let admin_bytes = admin_key.as_ref();
+    let creator_key = ctx.accounts.pool_creator.key();
+    let creator_bytes = creator_key.as_ref();
     let pool_id_bytes = pool_id.to_le_bytes();
     let seeds = &[
         SEED_POOL,
-        admin_bytes,
+        creator_bytes,
         &pool_id_bytes,
     ];
 
@@ -127,9 +134,13 @@ pub struct UndelegatePool<'info> {
     #[account(
         seeds = [SEED_PROTOCOL],
         bump,
-        constraint = protocol.admin == admin.key() @ CustomError::Unauthorized
+        constraint = protocol.has_role(admin.key(), roles.as_deref(), ROLE_RESOLVER) @ CustomError::Unauthorized
     )]
     pub protocol: Account<'info, Protocol>,
+
+    /// Roles PDA of `admin`; omit when `admin` is the protocol admin.
+    #[account(seeds = [SEED_ROLES, admin.key().as_ref()], bump = roles.bump)]
+    pub roles: Option<Account<'info, Roles>>,
     
     /// CHECK: The Pool account
     #[account(mut)]
@@ -1,8 +1,12 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
-use crate::state::{Pool, PoolStatus, Protocol};
-use crate::constants::{SEED_POOL, SEED_POOL_VAULT, SEED_PROTOCOL};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{AssetKind, Bet, Pool, PoolStatus, Protocol, SolVault, StakeReceipt};
+use crate::constants::{
+    SEED_BET, SEED_POOL, SEED_POOL_VAULT, SEED_PROTOCOL, SEED_SOL_VAULT, SEED_STAKE_RECEIPT,
+};
 use crate::errors::CustomError;
+use crate::events::StakeAdded;
+use ephemeral_rollups_sdk::anchor::DelegationProgram;
 
 #[derive(Accounts)]
 pub struct AddStake<'info> {
@@ -23,18 +27,52 @@ pub struct AddStake<'info> {
     )]
     pub pool: Box<Account<'info, Pool>>,
 
+    /// CHECK: The user's bet. It is usually delegated to the rollup, so it may be owned
+    /// by the delegation program; its L1 copy is deserialized in the handler.
+    #[account(
+        seeds = [SEED_BET, pool.key().as_ref(), user.key().as_ref()],
+        bump,
+        constraint = bet.owner == &crate::ID || bet.owner == &DelegationProgram::id()
+            @ CustomError::BetNotInitialized
+    )]
+    pub bet: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = StakeReceipt::SPACE,
+        seeds = [SEED_STAKE_RECEIPT, bet.key().as_ref()],
+        bump
+    )]
+    pub stake_receipt: Box<Account<'info, StakeReceipt>>,
+
+    /// Token pools only.
     #[account(
         mut,
         seeds = [SEED_POOL_VAULT, pool.key().as_ref()],
         bump,
         token::authority = pool,
     )]
-    pub pool_vault: Box<Account<'info, TokenAccount>>,
+    pub pool_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Native SOL pools only.
+    #[account(
+        mut,
+        seeds = [SEED_SOL_VAULT, pool.key().as_ref()],
+        bump = sol_vault.bump
+    )]
+    pub sol_vault: Option<Box<Account<'info, SolVault>>>,
 
+    /// Token pools only.
     #[account(mut)]
-    pub user_token_account: Box<Account<'info, TokenAccount>>,
+    pub user_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Token pools only.
+    #[account(address = pool.stake_token_mint @ CustomError::PoolMismatch)]
+    pub token_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
 }
 
 pub fn add_stake(ctx: Context<AddStake>, amount: u64) -> Result<()> {
@@ -47,21 +85,94 @@ pub fn add_stake(ctx: Context<AddStake>, amount: u64) -> Result<()> {
     );
     require!(clock.unix_timestamp < pool.cutoff_time, CustomError::MarketClosed);
 
-    token::transfer(
-        CpiContext::new(
-            ctx.accounts.token_program.key(),
-            Transfer {
-                from: ctx.accounts.user_token_account.to_account_info(),
-                to: ctx.accounts.pool_vault.to_account_info(),
-                authority: ctx.accounts.user.to_account_info(),
-            },
-        ),
-        amount,
-    )?;
-
-    ctx.accounts.pool.total_staked = ctx.accounts.pool.total_staked
-        .checked_add(amount)
-        .unwrap();
+    let base_stake = {
+        let data = ctx.accounts.bet.try_borrow_data()?;
+        let bet = Bet::try_deserialize(&mut &data[..]).map_err(|_| error!(CustomError::BetNotInitialized))?;
+        bet.stake.saturating_sub(bet.credited_top_ups)
+    };
+
+    let received = match pool.asset_kind {
+        AssetKind::Token => {
+            let (Some(pool_vault), Some(user_token_account), Some(token_mint), Some(token_program)) = (
+                ctx.accounts.pool_vault.as_mut(),
+                ctx.accounts.user_token_account.as_ref(),
+                ctx.accounts.token_mint.as_ref(),
+                ctx.accounts.token_program.as_ref(),
+            ) else {
+                return err!(CustomError::AssetAccountsMismatch);
+            };
+
+            let vault_before = pool_vault.amount;
+
+            token_interface::transfer_checked(
+                CpiContext::new(
+                    token_program.key(),
+                    TransferChecked {
+                        from: user_token_account.to_account_info(),
+                        mint: token_mint.to_account_info(),
+                        to: pool_vault.to_account_info(),
+                        authority: ctx.accounts.user.to_account_info(),
+                    },
+                ),
+                amount,
+                token_mint.decimals,
+            )?;
+
+            // Transfer-fee mints deliver less than `amount`; the receipt records what arrived.
+            pool_vault.reload()?;
+            pool_vault.amount.checked_sub(vault_before).unwrap()
+        }
+        AssetKind::Native => {
+            let Some(sol_vault) = ctx.accounts.sol_vault.as_ref() else {
+                return err!(CustomError::AssetAccountsMismatch);
+            };
+
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.key(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.user.to_account_info(),
+                        to: sol_vault.to_account_info(),
+                    },
+                ),
+                amount,
+            )?;
+            amount
+        }
+    };
+
+    let pool = &ctx.accounts.pool;
+
+    // The rollup may hold a newer stake, but credited top-ups only ever come from this
+    // receipt, so the L1 copy's own stake plus every deposit bounds what update_bet can credit.
+    let projected_stake = base_stake
+        .checked_add(ctx.accounts.stake_receipt.total_amount)
+        .and_then(|total| total.checked_add(received))
+        .ok_or(CustomError::MathOverflow)?;
+    pool.check_bet_stake(received, projected_stake)?;
+    pool.check_pool_capacity(received)?;
+
+    let pool = &mut ctx.accounts.pool;
+    pool.total_staked = pool.total_staked.checked_add(received).unwrap();
+    pool.pending_top_ups = pool.pending_top_ups.checked_add(received).unwrap();
+
+    let receipt = &mut ctx.accounts.stake_receipt;
+    if receipt.bet == Pubkey::default() {
+        receipt.bet = ctx.accounts.bet.key();
+        receipt.user = ctx.accounts.user.key();
+        receipt.pool = ctx.accounts.pool.key();
+        receipt.bump = ctx.bumps.stake_receipt;
+    }
+    receipt.total_amount = receipt.total_amount.checked_add(received).unwrap();
+    receipt.nonce = receipt.nonce.checked_add(1).unwrap();
+
+    emit!(StakeAdded {
+        bet: receipt.bet,
+        user: receipt.user,
+        amount: received,
+        nonce: receipt.nonce,
+        total_amount: receipt.total_amount,
+    });
 
     Ok(())
 }
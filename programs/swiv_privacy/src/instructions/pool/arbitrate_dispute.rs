@@ -0,0 +1,180 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{Pool, PoolStatus, Protocol, ResolutionProposal};
+use crate::constants::{SEED_BOND_VAULT, SEED_POOL, SEED_PROPOSAL, SEED_PROTOCOL};
+use crate::errors::CustomError;
+use crate::events::DisputeArbitrated;
+use crate::utils::harvest_withheld_fees;
+
+#[derive(Accounts)]
+pub struct ArbitrateDispute<'info> {
+    #[account(mut)]
+    pub arbiter: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_PROTOCOL],
+        bump,
+        constraint = protocol.arbiter == arbiter.key() @ CustomError::Unauthorized
+    )]
+    pub protocol: Box<Account<'info, Protocol>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_POOL, pool.created_by.as_ref(), &(pool.pool_id.to_le_bytes())],
+        bump = pool.bump
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        mut,
+        close = proposer,
+        seeds = [SEED_PROPOSAL, pool.key().as_ref()],
+        bump = proposal.bump,
+    )]
+    pub proposal: Box<Account<'info, ResolutionProposal>>,
+
+    /// CHECK: Receives the proposal and bond vault rent; validated against the proposal.
+    #[account(mut, address = proposal.proposer @ CustomError::Unauthorized)]
+    pub proposer: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_BOND_VAULT, pool.key().as_ref()],
+        bump,
+        token::authority = pool,
+    )]
+    pub bond_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = pool.stake_token_mint,
+        token::authority = proposal.proposer,
+    )]
+    pub proposer_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = pool.stake_token_mint,
+        token::authority = proposal.disputer,
+    )]
+    pub disputer_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = pool.stake_token_mint,
+        token::authority = protocol.treasury_wallet,
+    )]
+    pub treasury_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Writable so transfer fees withheld on the bond vault can be harvested before it closes.
+    #[account(mut, address = pool.stake_token_mint @ CustomError::PoolMismatch)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Settles a disputed proposal. The side that matches `final_outcome` gets its
+/// bond back; the other side's bond is slashed to the treasury. Both bonds are
+/// the net amounts the vault received, so transfer-fee mints settle exactly.
+pub fn arbitrate_dispute(ctx: Context<ArbitrateDispute>, final_outcome: u64) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let proposal = &ctx.accounts.proposal;
+    let clock = Clock::get()?;
+
+    require!(pool.status == PoolStatus::Disputed, CustomError::InvalidProposalState);
+    pool.validate_outcome(final_outcome)?;
+
+    let proposer_won = final_outcome == proposal.proposed_outcome;
+    // The proposer's net bond is on the proposal; the disputer's is the rest of the vault.
+    let proposer_bond = proposal.bond_amount;
+    let disputer_bond = ctx
+        .accounts
+        .bond_vault
+        .amount
+        .checked_sub(proposer_bond)
+        .ok_or(CustomError::MathOverflow)?;
+    let (returned_amount, slashed_amount) = if proposer_won {
+        (proposer_bond, disputer_bond)
+    } else {
+        (disputer_bond, proposer_bond)
+    };
+
+    let created_by_bytes = pool.created_by.as_ref();
+    let pool_id_bytes = pool.pool_id.to_le_bytes();
+    let bump = pool.bump;
+    let seeds = &[SEED_POOL, created_by_bytes, &pool_id_bytes, &[bump]];
+    let signer = &[&seeds[..]];
+
+    if returned_amount > 0 {
+        let winner_account = if proposer_won {
+            ctx.accounts.proposer_token_account.to_account_info()
+        } else {
+            ctx.accounts.disputer_token_account.to_account_info()
+        };
+
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.key(),
+                TransferChecked {
+                    from: ctx.accounts.bond_vault.to_account_info(),
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    to: winner_account,
+                    authority: pool.to_account_info(),
+                },
+                signer,
+            ),
+            returned_amount,
+            ctx.accounts.token_mint.decimals,
+        )?;
+    }
+
+    if slashed_amount > 0 {
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.key(),
+                TransferChecked {
+                    from: ctx.accounts.bond_vault.to_account_info(),
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    to: ctx.accounts.treasury_token_account.to_account_info(),
+                    authority: pool.to_account_info(),
+                },
+                signer,
+            ),
+            slashed_amount,
+            ctx.accounts.token_mint.decimals,
+        )?;
+    }
+
+    harvest_withheld_fees(
+        ctx.accounts.bond_vault.to_account_info(),
+        ctx.accounts.token_mint.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+    )?;
+    token_interface::close_account(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.key(),
+            token_interface::CloseAccount {
+                account: ctx.accounts.bond_vault.to_account_info(),
+                destination: ctx.accounts.proposer.to_account_info(),
+                authority: pool.to_account_info(),
+            },
+            signer,
+        ),
+    )?;
+
+    pool.resolution_result = final_outcome;
+    pool.resolution_ts = clock.unix_timestamp;
+    pool.status = PoolStatus::Resolving;
+
+    emit!(DisputeArbitrated {
+        pool: pool.key(),
+        arbiter: ctx.accounts.arbiter.key(),
+        final_outcome,
+        proposer_won,
+        slashed_amount,
+    });
+
+    msg!("Dispute arbitrated. Pool Resolving. Outcome: {}", final_outcome);
+
+    Ok(())
+}
@@ -1,7 +1,10 @@
 use anchor_lang::prelude::*;
-use crate::state::{Pool, PoolStatus, Protocol};
-use crate::constants::{SEED_PROTOCOL, SEED_POOL};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::{Pool, PoolStatus, Protocol, SolVault};
+use crate::constants::{SEED_PROTOCOL, SEED_POOL, SEED_POOL_VAULT, SEED_SOL_VAULT};
 use crate::errors::CustomError;
+use crate::utils::PoolVault;
+use crate::events::SubsidyReturned;
 
 #[derive(Accounts)]
 pub struct CancelPool<'info> {
@@ -21,6 +24,41 @@ pub struct CancelPool<'info> {
         bump = pool.bump
     )]
     pub pool: Account<'info, Pool>,
+
+    /// Token pools only.
+    #[account(
+        mut,
+        seeds = [SEED_POOL_VAULT, pool.key().as_ref()],
+        bump,
+        token::authority = pool,
+    )]
+    pub pool_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Native SOL pools only.
+    #[account(
+        mut,
+        seeds = [SEED_SOL_VAULT, pool.key().as_ref()],
+        bump = sol_vault.bump
+    )]
+    pub sol_vault: Option<Account<'info, SolVault>>,
+
+    /// Token pools only. Receives any creator subsidy still sitting in the vault.
+    #[account(
+        mut,
+        token::mint = pool.stake_token_mint,
+        token::authority = pool.created_by,
+    )]
+    pub creator_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Receives the subsidy of native SOL pools.
+    #[account(mut, address = pool.created_by @ CustomError::Unauthorized)]
+    pub pool_creator: Option<UncheckedAccount<'info>>,
+
+    /// Token pools only.
+    #[account(address = pool.stake_token_mint @ CustomError::PoolMismatch)]
+    pub token_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
 }
 
 pub fn cancel_pool(ctx: Context<CancelPool>) -> Result<()> {
@@ -34,6 +72,32 @@ pub fn cancel_pool(ctx: Context<CancelPool>) -> Result<()> {
     );
 
     pool.status = PoolStatus::Cancelled;
+    pool.open_claim_window(Clock::get()?.unix_timestamp);
+
+    let subsidy = pool.subsidy_amount;
+    if subsidy > 0 {
+        let vault = PoolVault::resolve(
+            pool,
+            ctx.accounts.pool_vault.as_ref(),
+            ctx.accounts.sol_vault.as_ref(),
+            ctx.accounts.token_mint.as_deref(),
+            ctx.accounts.token_program.as_ref(),
+        )?;
+        vault.pay(
+            pool,
+            ctx.accounts.creator_token_account.as_ref().map(|account| account.to_account_info()),
+            ctx.accounts.pool_creator.as_ref().map(|account| account.to_account_info()),
+            subsidy,
+        )?;
+
+        pool.subsidy_amount = 0;
+
+        emit!(SubsidyReturned {
+            pool: pool.key(),
+            creator: pool.created_by,
+            amount: subsidy,
+        });
+    }
 
     msg!("Pool {} cancelled by admin.", pool.pool_id);
 
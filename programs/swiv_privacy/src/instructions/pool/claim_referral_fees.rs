@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::ReferralStats;
+use crate::constants::{SEED_REFERRAL, SEED_REFERRAL_VAULT};
+use crate::errors::CustomError;
+use crate::events::ReferralFeesClaimed;
+
+#[derive(Accounts)]
+pub struct ClaimReferralFees<'info> {
+    pub referrer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_REFERRAL, referrer.key().as_ref(), referral_stats.mint.as_ref()],
+        bump = referral_stats.bump,
+    )]
+    pub referral_stats: Account<'info, ReferralStats>,
+
+    #[account(
+        mut,
+        seeds = [SEED_REFERRAL_VAULT, referral_stats.key().as_ref()],
+        bump,
+        token::authority = referral_stats,
+    )]
+    pub referral_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = referral_stats.mint,
+    )]
+    pub referrer_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = referral_stats.mint @ CustomError::PoolMismatch)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Withdraws everything accrued across all pools of this mint in one transfer.
+pub fn claim_referral_fees(ctx: Context<ClaimReferralFees>) -> Result<()> {
+    let amount = ctx.accounts.referral_vault.amount;
+    require!(amount > 0, CustomError::NothingToClaim);
+
+    let stats = &ctx.accounts.referral_stats;
+    let referrer_key = stats.referrer;
+    let mint_key = stats.mint;
+    let bump = stats.bump;
+    let seeds = &[SEED_REFERRAL, referrer_key.as_ref(), mint_key.as_ref(), &[bump]];
+    let signer = &[&seeds[..]];
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.key(),
+            TransferChecked {
+                from: ctx.accounts.referral_vault.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: ctx.accounts.referrer_token_account.to_account_info(),
+                authority: ctx.accounts.referral_stats.to_account_info(),
+            },
+            signer,
+        ),
+        amount,
+        ctx.accounts.token_mint.decimals,
+    )?;
+
+    let stats = &mut ctx.accounts.referral_stats;
+    stats.total_claimed = stats.total_claimed.checked_add(amount).unwrap();
+
+    emit!(ReferralFeesClaimed {
+        referrer: referrer_key,
+        mint: mint_key,
+        amount,
+    });
+
+    Ok(())
+}
@@ -1,21 +1,36 @@
-use crate::constants::{SEED_POOL, SEED_POOL_VAULT};
+use crate::constants::{
+    SEED_POOL, SEED_POOL_VAULT, SEED_PROTOCOL, SEED_REFERRAL, SEED_REFERRAL_VAULT, SEED_SOL_VAULT,
+    SEED_STAKE_RECEIPT,
+};
 use crate::errors::CustomError;
-use crate::state::{BetStatus, Pool, PoolStatus, Bet};
-use crate::events::RewardClaimed;
+use crate::state::{
+    BetStatus, Pool, PoolKind, PoolStatus, Bet, Protocol, ReferralStats, ScoringRule, SolVault,
+    StakeReceipt,
+};
+use crate::events::{ReferralFeeAccrued, RewardClaimed};
+use crate::utils::PoolVault;
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 #[derive(Accounts)]
 pub struct ClaimReward<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
 
+    /// Gas sponsor that paid the bet rent at init_bet; receives it back when the bet closes.
     #[account(
         mut,
-        constraint = sponsor.key() == pool.created_by @ CustomError::Unauthorized
+        constraint = sponsor.key() == bet.sponsor @ CustomError::Unauthorized
     )]
     pub sponsor: Signer<'info>,
 
+    #[account(seeds = [SEED_PROTOCOL], bump)]
+    pub protocol: Box<Account<'info, Protocol>>,
+
+    /// CHECK: Paid for the pool and vault at create_pool; receives their rent once the last bet closes.
+    #[account(mut, address = pool.created_by @ CustomError::Unauthorized)]
+    pub pool_creator: UncheckedAccount<'info>,
+
     #[account(
         mut,
         seeds = [SEED_POOL, pool.created_by.as_ref(), &(pool.pool_id.to_le_bytes())],
@@ -23,13 +38,22 @@ pub struct ClaimReward<'info> {
     )]
     pub pool: Box<Account<'info, Pool>>,
 
+    /// Token pools only.
     #[account(
         mut,
         seeds = [SEED_POOL_VAULT, pool.key().as_ref()],
         bump,
         token::authority = pool,
     )]
-    pub pool_vault: Account<'info, TokenAccount>,
+    pub pool_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Native SOL pools only.
+    #[account(
+        mut,
+        seeds = [SEED_SOL_VAULT, pool.key().as_ref()],
+        bump = sol_vault.bump
+    )]
+    pub sol_vault: Option<Box<Account<'info, SolVault>>>,
 
     #[account(
         mut,
@@ -40,10 +64,38 @@ pub struct ClaimReward<'info> {
     )]
     pub bet: Box<Account<'info, Bet>>,
 
+    /// Token pools only; native pools pay the user's wallet.
     #[account(mut)]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub user_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Required when the bet has a referrer and the pool holds a referral reserve.
+    #[account(
+        mut,
+        seeds = [SEED_REFERRAL, bet.referrer.as_ref(), pool.stake_token_mint.as_ref()],
+        bump = referral_stats.bump,
+    )]
+    pub referral_stats: Option<Box<Account<'info, ReferralStats>>>,
+
+    #[account(mut)]
+    pub referral_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// The bet's top-up receipt, if `add_stake` was ever used. Closed to the user;
+    /// any deposit never credited by `update_bet` is refunded with the payout.
+    #[account(
+        mut,
+        close = user,
+        seeds = [SEED_STAKE_RECEIPT, bet.key().as_ref()],
+        bump = stake_receipt.bump,
+        constraint = stake_receipt.bet == bet.key() @ CustomError::StakeReceiptMismatch
+    )]
+    pub stake_receipt: Option<Box<Account<'info, StakeReceipt>>>,
+
+    /// Token pools only; writable so transfer fees withheld on the vault can be
+    /// harvested before it closes.
+    #[account(mut, address = pool.stake_token_mint @ CustomError::PoolMismatch)]
+    pub token_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
 }
 
 pub fn claim_reward(ctx: Context<ClaimReward>) -> Result<()> {
@@ -51,59 +103,152 @@ pub fn claim_reward(ctx: Context<ClaimReward>) -> Result<()> {
     let bet = &mut ctx.accounts.bet;
     let mut payout_amount: u64 = 0;
 
-    require!(pool.status == PoolStatus::Resolved, CustomError::SettlementTooEarly);
+    // Top-ups that reached the vault but were never credited in the rollup are the
+    // user's own funds: finalize_weights keeps them out of the pot, so return them here.
+    let uncredited = ctx
+        .accounts
+        .stake_receipt
+        .as_ref()
+        .map_or(0, |receipt| receipt.pending(bet.credited_top_ups));
+
+    let vault = PoolVault::resolve(
+        pool,
+        ctx.accounts.pool_vault.as_ref(),
+        ctx.accounts.sol_vault.as_deref(),
+        ctx.accounts.token_mint.as_deref(),
+        ctx.accounts.token_program.as_ref(),
+    )?;
+
+    let is_voided = pool.status == PoolStatus::Voided;
+    require!(
+        pool.status == PoolStatus::Resolved || is_voided,
+        CustomError::SettlementTooEarly
+    );
+    require!(
+        !pool.claim_deadline_passed(Clock::get()?.unix_timestamp),
+        CustomError::ClaimDeadlinePassed
+    );
  
-    if pool.total_participants == 1 {
-        payout_amount = ctx.accounts.pool_vault.amount;
+    if is_voided && pool.total_participants == 1 {
+        // As in emergency_refund, the last refund takes what is left, including exit
+        // penalties withdraw_bet kept in the vault, so the vault can close.
+        payout_amount = vault
+            .balance()?
+            .checked_sub(pool.subsidy_amount)
+            .and_then(|left| left.checked_sub(uncredited))
+            .ok_or(CustomError::InsufficientLiquidity)?;
+    } else if is_voided {
+        // Voided pools skip weighting and finalization: refund the exact stake, fee-free.
+        payout_amount = bet.stake;
+    } else if pool.total_participants == 1 {
+        // The last claimant takes what is left, except the referrers' share paid below.
+        payout_amount = vault
+            .balance()?
+            .checked_sub(pool.referral_reserve)
+            .and_then(|left| left.checked_sub(uncredited))
+            .ok_or(CustomError::InsufficientLiquidity)?;
     } else if pool.total_weight > 0 {
         require!(
             bet.status == BetStatus::Resolved,
             CustomError::NotCalculatedYet
         );
  
-        if bet.calculated_weight > 0 {
+        // Under winner-take-closest, bets scored before a closer one was found keep a
+        // stale weight that no longer counts toward pool.total_weight.
+        let weight = if pool.kind != PoolKind::Categorical
+            && pool.scoring_rule == ScoringRule::WinnerTakeClosest
+            && bet.scored_error != pool.closest_error
+        {
+            0
+        } else {
+            bet.calculated_weight
+        };
+
+        if weight > 0 {
             let total_distributable_pot = pool.distributable_amount as u128;
  
-            payout_amount = bet
-                .calculated_weight
+            payout_amount = weight
                 .checked_mul(total_distributable_pot)
-                .unwrap()
+                .ok_or(CustomError::MathOverflow)?
                 .checked_div(pool.total_weight)
-                .unwrap() as u64;
+                .ok_or(CustomError::MathOverflow)? as u64;
         }
     } else {
         payout_amount = bet.stake;
     }
 
+    require!(
+        is_voided || payout_amount <= pool.distributable_amount,
+        CustomError::InsufficientLiquidity
+    );
+    payout_amount = payout_amount.checked_add(uncredited).ok_or(CustomError::MathOverflow)?;
+
     if payout_amount > 0 {
         require!(
-            payout_amount <= pool.distributable_amount,
-            CustomError::InsufficientLiquidity
-        );
-        require!(
-            payout_amount <= ctx.accounts.pool_vault.amount,
+            payout_amount <= vault.balance()?,
             CustomError::InsufficientLiquidity
         );
 
-        let created_by_bytes = pool.created_by.as_ref();
-        let pool_id_bytes = pool.pool_id.to_le_bytes();
-        let bump = pool.bump;
-        let seeds = &[SEED_POOL, created_by_bytes, &pool_id_bytes, &[bump]];
-        let signer = &[&seeds[..]];
-
-        token::transfer(
-            CpiContext::new_with_signer(
-                ctx.accounts.token_program.key(),
-                Transfer {
-                    from: ctx.accounts.pool_vault.to_account_info(),
-                    to: ctx.accounts.user_token_account.to_account_info(),
-                    authority: pool.to_account_info(),
-                },
-                signer,
-            ),
+        vault.pay(
+            pool,
+            ctx.accounts.user_token_account.as_ref().map(|account| account.to_account_info()),
+            Some(ctx.accounts.user.to_account_info()),
             payout_amount,
         )?;
-        
+    }
+
+    if !is_voided
+        && bet.referrer != Pubkey::default()
+        && bet.referred_amount > 0
+        && pool.referral_reserve > 0
+    {
+        // Pro-rata over the referred stake still unpaid, so the last referred claim
+        // sweeps any rounding remainder out of the reserve.
+        let referred_amount = bet.referred_amount.min(pool.referred_stake);
+        let referral_amount = if referred_amount == pool.referred_stake {
+            pool.referral_reserve
+        } else {
+            (pool.referral_reserve as u128)
+                .checked_mul(referred_amount as u128)
+                .ok_or(CustomError::MathOverflow)?
+                .checked_div(pool.referred_stake as u128)
+                .ok_or(CustomError::MathOverflow)? as u64
+        };
+
+        let (Some(referral_stats), Some(referral_vault)) = (
+            ctx.accounts.referral_stats.as_mut(),
+            ctx.accounts.referral_vault.as_ref(),
+        ) else {
+            return err!(CustomError::ReferralAccountsRequired);
+        };
+        let (expected_vault, _) = Pubkey::find_program_address(
+            &[SEED_REFERRAL_VAULT, referral_stats.key().as_ref()],
+            ctx.program_id,
+        );
+        require_keys_eq!(referral_vault.key(), expected_vault, CustomError::ReferralAccountsRequired);
+
+        // Native pools never record a referrer, so this is always a token transfer.
+        vault.pay(pool, Some(referral_vault.to_account_info()), None, referral_amount)?;
+
+        referral_stats.total_earned = referral_stats.total_earned
+            .checked_add(referral_amount)
+            .ok_or(CustomError::MathOverflow)?;
+        referral_stats.referred_bets = referral_stats.referred_bets
+            .checked_add(1)
+            .ok_or(CustomError::MathOverflow)?;
+        pool.referral_reserve = pool.referral_reserve
+            .checked_sub(referral_amount)
+            .ok_or(CustomError::MathOverflow)?;
+        pool.referred_stake = pool.referred_stake
+            .checked_sub(referred_amount)
+            .ok_or(CustomError::MathOverflow)?;
+
+        emit!(ReferralFeeAccrued {
+            referrer: bet.referrer,
+            pool: pool.key(),
+            bet: bet.key(),
+            amount: referral_amount,
+        });
     }
 
     bet.status = BetStatus::Claimed;
@@ -115,32 +260,19 @@ pub fn claim_reward(ctx: Context<ClaimReward>) -> Result<()> {
     });
 
     pool.total_participants = pool.total_participants.saturating_sub(1);
-    if pool.total_participants == 0 {
-        let created_by_bytes = pool.created_by.as_ref();
-        let pool_id_bytes = pool.pool_id.to_le_bytes();
-        let bump = pool.bump;
-        let seeds = &[SEED_POOL, created_by_bytes, &pool_id_bytes, &[bump]];
-        let signer = &[&seeds[..]];
-
-        token::close_account(
-            CpiContext::new_with_signer(
-                ctx.accounts.token_program.key(),
-                token::CloseAccount {
-                    account: ctx.accounts.pool_vault.to_account_info(),
-                    destination: ctx.accounts.sponsor.to_account_info(),
-                    authority: pool.to_account_info(),
-                },
-                signer,
-            ),
-        )?;
+    // A reserve nobody is left to claim stays in the vault for `sweep_unclaimed`.
+    if pool.total_participants == 0 && pool.referral_reserve == 0 {
+        vault.close(pool, ctx.accounts.pool_creator.to_account_info())?;
 
         let pool_info = pool.to_account_info();
-        let sponsor_info = ctx.accounts.sponsor.to_account_info();
+        let creator_info = ctx.accounts.pool_creator.to_account_info();
         
-        let sponsor_lamports = sponsor_info.lamports();
+        let creator_lamports = creator_info.lamports();
         let pool_lamports = pool_info.lamports();
         
-        **sponsor_info.lamports.borrow_mut() = sponsor_lamports.checked_add(pool_lamports).unwrap();
+        **creator_info.lamports.borrow_mut() = creator_lamports
+            .checked_add(pool_lamports)
+            .ok_or(CustomError::MathOverflow)?;
         **pool_info.lamports.borrow_mut() = 0;
 
         msg!("All claims completed. Pool and vault accounts closed, rent reclaimed.");
@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+use crate::state::{Bet, StakeReceipt};
+use crate::constants::{SEED_BET, SEED_STAKE_RECEIPT};
+use crate::errors::CustomError;
+use crate::events::OrphanedBetClosed;
+
+#[derive(Accounts)]
+pub struct CloseOrphanedBet<'info> {
+    /// Anyone may close a bet left behind by `sweep_unclaimed`; the rent goes back to its payers.
+    pub caller: Signer<'info>,
+
+    /// CHECK: Paid the bet rent at init_bet; receives it back.
+    #[account(mut, address = bet.sponsor @ CustomError::Unauthorized)]
+    pub sponsor: UncheckedAccount<'info>,
+
+    /// CHECK: Owner of the bet; receives the stake receipt rent it paid at add_stake.
+    #[account(mut, address = bet.user_pubkey @ CustomError::Unauthorized)]
+    pub user: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = sponsor,
+        seeds = [SEED_BET, bet.pool_pubkey.as_ref(), bet.user_pubkey.as_ref()],
+        bump = bet.bump
+    )]
+    pub bet: Box<Account<'info, Bet>>,
+
+    /// CHECK: The bet's pool, which must already be closed.
+    #[account(
+        address = bet.pool_pubkey @ CustomError::PoolMismatch,
+        constraint = pool.data_is_empty() @ CustomError::PoolStillOpen
+    )]
+    pub pool: UncheckedAccount<'info>,
+
+    /// The bet's top-up receipt, if `add_stake` was ever used.
+    #[account(
+        mut,
+        close = user,
+        seeds = [SEED_STAKE_RECEIPT, bet.key().as_ref()],
+        bump = stake_receipt.bump,
+        constraint = stake_receipt.bet == bet.key() @ CustomError::StakeReceiptMismatch
+    )]
+    pub stake_receipt: Option<Box<Account<'info, StakeReceipt>>>,
+}
+
+/// Closes a bet whose pool was swept before it claimed. Its share of the vault was
+/// already forfeited, so only the account rent is returned.
+pub fn close_orphaned_bet(ctx: Context<CloseOrphanedBet>) -> Result<()> {
+    let bet = &ctx.accounts.bet;
+
+    emit!(OrphanedBetClosed {
+        bet: bet.key(),
+        pool: bet.pool_pubkey,
+        user: bet.user_pubkey,
+        sponsor: bet.sponsor,
+    });
+
+    msg!("Orphaned bet {} closed.", bet.key());
+
+    Ok(())
+}
@@ -1,9 +1,17 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount};
-use crate::state::{Pool, PoolStatus, Protocol};
-use crate::constants::{SEED_PROTOCOL, SEED_POOL, SEED_POOL_VAULT};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{
+    AssetKind, ConvictionConfig, CreatorBond, OracleConfig, OutcomeConfig, Pool, PoolKind, PoolStatus, Protocol,
+    ResolverCommittee, Roles, ScoringRule, SolVault, StakeLimits, TimeBonusConfig, VoidReason,
+};
+use crate::constants::{
+    BPS_DENOMINATOR, DEFAULT_CLAIM_WINDOW, MAX_CONVICTION_BONUS_BPS, MAX_CREATOR_FEE_BPS, MAX_EXIT_PENALTY_BPS,
+    MAX_OUTCOMES, MAX_OUTCOME_EXPONENT, MAX_OUTCOME_LABEL_LENGTH, MAX_TIME_BONUS_BPS, MAX_TITLE_LENGTH,
+    PYTH_PROGRAM_ID, ROLE_POOL_CREATOR, SEED_CREATOR_BOND, SEED_POOL, SEED_POOL_VAULT, SEED_PROTOCOL, SEED_ROLES, SEED_SOL_VAULT,
+};
 use crate::errors::CustomError;
-use crate::events::PoolCreated;
+use crate::events::{CreatorBondPosted, PoolCreated};
+use crate::utils::require_supported_mint;
 
 #[derive(Accounts)]
 #[instruction(
@@ -11,26 +19,40 @@ use crate::events::PoolCreated;
     start_time: i64,
     end_time: i64,
     max_accuracy_buffer: u64,
-    conviction_bonus_bps: u64
+    conviction: ConvictionConfig,
+    oracle: Option<OracleConfig>,
+    scoring_rule: ScoringRule,
+    time_bonus: TimeBonusConfig,
+    outcome: OutcomeConfig,
+    creator_fee_bps: u64,
+    subsidy_amount: u64,
+    limits: StakeLimits,
+    exit_penalty_bps: u64,
+    claim_window: i64,
+    asset_kind: AssetKind
 )]
 pub struct CreatePool<'info> {
+    /// Anyone may create a pool; creators other than the admin or a `ROLE_POOL_CREATOR`
+    /// holder escrow `protocol.creation_bond` and are blocked while the protocol is paused.
     #[account(
         mut,
         seeds = [SEED_PROTOCOL],
         bump,
-        constraint = protocol.admin == created_by.key() @ CustomError::Unauthorized
+        constraint = !protocol.paused
+            || protocol.has_role(created_by.key(), roles.as_deref(), ROLE_POOL_CREATOR) @ CustomError::Paused
     )]
     pub protocol: Account<'info, Protocol>,
 
     #[account(
         init,
         payer = created_by,
-        space = 8 + 360,
+        space = Pool::SPACE,
         seeds = [SEED_POOL, created_by.key().as_ref(), &protocol.total_pools.to_le_bytes()],
         bump
     )]
     pub pool: Account<'info, Pool>,
 
+    /// Token pools only.
     #[account(
         init,
         payer = created_by,
@@ -38,36 +60,196 @@ pub struct CreatePool<'info> {
         bump,
         token::mint = token_mint,
         token::authority = pool,
+        token::token_program = token_program,
     )]
-    pub pool_vault: Account<'info, TokenAccount>,
+    pub pool_vault: Option<InterfaceAccount<'info, TokenAccount>>,
 
-    pub token_mint: Account<'info, token::Mint>,
+    /// Token pools only.
+    pub token_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Native SOL pools only; the creator pays its rent-exempt reserve.
+    #[account(
+        init,
+        payer = created_by,
+        space = SolVault::SPACE,
+        seeds = [SEED_SOL_VAULT, pool.key().as_ref()],
+        bump
+    )]
+    pub sol_vault: Option<Account<'info, SolVault>>,
+
+    #[account(
+        init,
+        payer = created_by,
+        space = CreatorBond::SPACE,
+        seeds = [SEED_CREATOR_BOND, pool.key().as_ref()],
+        bump
+    )]
+    pub creator_bond: Box<Account<'info, CreatorBond>>,
+
+    /// Optional resolver committee; when set, only its reporters can resolve the pool.
+    pub resolver_committee: Option<Account<'info, ResolverCommittee>>,
+
+    /// CHECK: Oracle pools only; must be the Pyth price account named in the oracle config.
+    #[account(owner = PYTH_PROGRAM_ID @ CustomError::InvalidOracleAccount)]
+    pub price_feed: Option<UncheckedAccount<'info>>,
 
     #[account(mut)]
     pub created_by: Signer<'info>,
 
-    #[account(mut)]
-    pub created_by_token_account: Account<'info, TokenAccount>,
+    /// Roles PDA of `created_by`; omit unless it holds `ROLE_POOL_CREATOR`.
+    #[account(seeds = [SEED_ROLES, created_by.key().as_ref()], bump = roles.bump)]
+    pub roles: Option<Account<'info, Roles>>,
 
-    pub token_program: Program<'info, Token>,
+    /// Funds the optional subsidy pot of token pools.
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::authority = created_by,
+    )]
+    pub created_by_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
 
+#[allow(clippy::too_many_arguments, clippy::manual_clamp)]
 pub fn create_pool(
     ctx: Context<CreatePool>,
     title: String,
     start_time: i64,
     end_time: i64,
     max_accuracy_buffer: u64,
-    conviction_bonus_bps: u64,
+    conviction: ConvictionConfig,
+    oracle: Option<OracleConfig>,
+    scoring_rule: ScoringRule,
+    time_bonus: TimeBonusConfig,
+    outcome: OutcomeConfig,
+    creator_fee_bps: u64,
+    subsidy_amount: u64,
+    limits: StakeLimits,
+    exit_penalty_bps: u64,
+    claim_window: i64,
+    asset_kind: AssetKind,
 ) -> Result<()> {
     let clock = Clock::get()?;
 
+    require!(title.len() <= MAX_TITLE_LENGTH, CustomError::TitleTooLong);
     require!(end_time > start_time, CustomError::DurationTooShort);
     require!(end_time > clock.unix_timestamp, CustomError::DurationTooShort);
     require!(end_time.saturating_sub(start_time) >= 10, CustomError::DurationTooShort);
 
+    require!(
+        conviction.bonus_bps <= MAX_CONVICTION_BONUS_BPS
+            && conviction.floor_bps <= BPS_DENOMINATOR + conviction.bonus_bps,
+        CustomError::InvalidConvictionCurve
+    );
+
+    require!(creator_fee_bps <= MAX_CREATOR_FEE_BPS, CustomError::InvalidCreatorFee);
+
+    require!(
+        (limits.max_stake_per_bet == 0 || limits.min_stake <= limits.max_stake_per_bet)
+            && (limits.max_pool_total == 0 || limits.min_stake <= limits.max_pool_total),
+        CustomError::InvalidStakeLimits
+    );
+
+    require!(exit_penalty_bps <= MAX_EXIT_PENALTY_BPS, CustomError::InvalidExitPenalty);
+    require!(
+        claim_window == 0 || claim_window >= ctx.accounts.protocol.min_claim_window,
+        CustomError::InvalidClaimWindow
+    );
+
+    let has_token_accounts = ctx.accounts.pool_vault.is_some()
+        && ctx.accounts.token_mint.is_some()
+        && ctx.accounts.created_by_token_account.is_some()
+        && ctx.accounts.token_program.is_some();
+    let has_token_account = ctx.accounts.pool_vault.is_some()
+        || ctx.accounts.token_mint.is_some()
+        || ctx.accounts.created_by_token_account.is_some();
+    match asset_kind {
+        AssetKind::Token => require!(
+            has_token_accounts && ctx.accounts.sol_vault.is_none(),
+            CustomError::AssetAccountsMismatch
+        ),
+        AssetKind::Native => require!(
+            !has_token_account && ctx.accounts.sol_vault.is_some(),
+            CustomError::AssetAccountsMismatch
+        ),
+    }
+    if let Some(token_mint) = ctx.accounts.token_mint.as_ref() {
+        require_supported_mint(token_mint)?;
+    }
+
+    require!(
+        time_bonus.max_bonus_bps <= MAX_TIME_BONUS_BPS,
+        CustomError::InvalidTimeBonus
+    );
+
+    match scoring_rule {
+        ScoringRule::LinearDecay | ScoringRule::StepBuckets => {
+            require!(max_accuracy_buffer > 0, CustomError::InvalidScoringRule);
+        }
+        ScoringRule::ExponentialDecay { half_life } => {
+            require!(half_life > 0, CustomError::InvalidScoringRule);
+        }
+        ScoringRule::Hyperbolic | ScoringRule::WinnerTakeClosest => {}
+    }
+
+    if let Some(config) = &oracle {
+        require!(config.feed != Pubkey::default(), CustomError::InvalidOracleAccount);
+        require!(
+            ctx.accounts.price_feed.as_ref().map(|feed| feed.key()) == Some(config.feed),
+            CustomError::InvalidOracleAccount
+        );
+        require!(config.max_staleness > 0, CustomError::OraclePriceStale);
+        require!(
+            ctx.accounts.resolver_committee.is_none(),
+            CustomError::CommitteeResolutionRequired
+        );
+    }
+
+    require!(
+        outcome.exponent.unsigned_abs() <= MAX_OUTCOME_EXPONENT as u32,
+        CustomError::InvalidOutcomeConfig
+    );
+
+    if let Some(config) = &oracle {
+        require!(config.exponent == outcome.exponent, CustomError::InvalidOutcomeConfig);
+    }
+
+    match outcome.kind {
+        PoolKind::Numeric => {
+            require!(outcome.labels.is_empty(), CustomError::InvalidOutcomeLabels);
+        }
+        PoolKind::SignedNumeric => {
+            require!(outcome.labels.is_empty(), CustomError::InvalidOutcomeLabels);
+            require!(
+                scoring_rule != ScoringRule::Hyperbolic || outcome.accuracy_scale > 0,
+                CustomError::InvalidOutcomeConfig
+            );
+            // Committee medians and deviations are computed on unsigned values.
+            require!(
+                ctx.accounts.resolver_committee.is_none(),
+                CustomError::InvalidOutcomeConfig
+            );
+        }
+        PoolKind::Categorical => {
+            require!(
+                (2..=MAX_OUTCOMES).contains(&outcome.labels.len())
+                    && outcome.labels
+                        .iter()
+                        .all(|label| !label.is_empty() && label.len() <= MAX_OUTCOME_LABEL_LENGTH),
+                CustomError::InvalidOutcomeLabels
+            );
+            // Oracle prices and committee medians are numeric; categorical pools are resolved by index.
+            require!(oracle.is_none(), CustomError::InvalidOutcomeLabels);
+            require!(
+                ctx.accounts.resolver_committee.is_none(),
+                CustomError::InvalidOutcomeLabels
+            );
+        }
+    }
+
     let pool = &mut ctx.accounts.pool;
     let protocol = &mut ctx.accounts.protocol;
 
@@ -86,7 +268,13 @@ pub fn create_pool(
     pool.created_by = ctx.accounts.created_by.key();
     pool.title = title.clone();
     pool.pool_id = pool_id;
-    pool.stake_token_mint = ctx.accounts.token_mint.key();
+    pool.stake_token_mint = ctx
+        .accounts
+        .token_mint
+        .as_ref()
+        .map(|mint| mint.key())
+        .unwrap_or_default();
+    pool.asset_kind = asset_kind;
     pool.start_time = start_time;
     pool.end_time = end_time;
     pool.cutoff_time = cutoff_time;
@@ -94,7 +282,33 @@ pub fn create_pool(
     pool.distributable_amount = 0;
     pool.total_participants = 0;
     pool.max_accuracy_buffer = max_accuracy_buffer;
-    pool.conviction_bonus_bps = conviction_bonus_bps;
+    pool.conviction_bonus_bps = conviction.bonus_bps;
+    pool.conviction_decay_bps = conviction.decay_bps;
+    pool.conviction_floor_bps = conviction.floor_bps;
+    pool.scoring_rule = scoring_rule;
+    pool.closest_error = u64::MAX;
+    pool.time_bonus_curve = time_bonus.curve;
+    pool.time_bonus_max_bps = time_bonus.max_bonus_bps;
+    pool.reset_bonus_on_update = time_bonus.reset_on_update;
+    pool.kind = outcome.kind;
+    pool.outcome_totals = vec![0; outcome.labels.len()];
+    pool.outcome_labels = outcome.labels;
+    pool.outcome_exponent = outcome.exponent;
+    pool.accuracy_scale = outcome.accuracy_scale;
+    pool.void_reason = VoidReason::None;
+    pool.creator_fee_bps = creator_fee_bps;
+    pool.fee_bps = protocol.protocol_fee_bps;
+    pool.version = Pool::VERSION;
+    pool.referred_stake = 0;
+    pool.referral_reserve = 0;
+    pool.pending_top_ups = 0;
+    pool.subsidy_amount = subsidy_amount;
+    pool.min_stake = limits.min_stake;
+    pool.max_stake_per_bet = limits.max_stake_per_bet;
+    pool.max_pool_total = limits.max_pool_total;
+    pool.exit_penalty_bps = exit_penalty_bps;
+    pool.claim_window = if claim_window == 0 { DEFAULT_CLAIM_WINDOW } else { claim_window };
+    pool.claim_deadline = 0;
     pool.resolution_result = 0;
     pool.resolution_ts = 0;
     pool.total_weight = 0;
@@ -102,12 +316,119 @@ pub fn create_pool(
     pool.status = initial_status;
     pool.bump = ctx.bumps.pool;
 
+    if let Some(config) = oracle {
+        pool.oracle_feed = config.feed;
+        pool.oracle_exponent = config.exponent;
+        pool.oracle_max_staleness = config.max_staleness;
+    } else {
+        pool.oracle_feed = Pubkey::default();
+        pool.oracle_exponent = 0;
+        pool.oracle_max_staleness = 0;
+    }
+
+    pool.resolver_committee = ctx
+        .accounts
+        .resolver_committee
+        .as_ref()
+        .map(|committee| committee.key())
+        .unwrap_or_default();
+
     protocol.total_pools = protocol.total_pools.checked_add(1).unwrap();
 
+    // The subsidy is a prize on top of the stakes: it is not part of total_staked
+    // and no participant is created for it.
+    if let Some(sol_vault) = ctx.accounts.sol_vault.as_mut() {
+        sol_vault.pool = pool.key();
+        sol_vault.bump = ctx.bumps.sol_vault.unwrap();
+    }
+
+    if subsidy_amount > 0 {
+        match asset_kind {
+            AssetKind::Token => {
+                let (Some(pool_vault), Some(token_mint), Some(created_by_token_account), Some(token_program)) = (
+                    ctx.accounts.pool_vault.as_mut(),
+                    ctx.accounts.token_mint.as_ref(),
+                    ctx.accounts.created_by_token_account.as_ref(),
+                    ctx.accounts.token_program.as_ref(),
+                ) else {
+                    return err!(CustomError::AssetAccountsMismatch);
+                };
+
+                token_interface::transfer_checked(
+                    CpiContext::new(
+                        token_program.key(),
+                        TransferChecked {
+                            from: created_by_token_account.to_account_info(),
+                            mint: token_mint.to_account_info(),
+                            to: pool_vault.to_account_info(),
+                            authority: ctx.accounts.created_by.to_account_info(),
+                        },
+                    ),
+                    subsidy_amount,
+                    token_mint.decimals,
+                )?;
+
+                // The vault is new, so its balance is exactly what survived any transfer fee.
+                pool_vault.reload()?;
+                pool.subsidy_amount = pool_vault.amount;
+            }
+            AssetKind::Native => {
+                let Some(sol_vault) = ctx.accounts.sol_vault.as_ref() else {
+                    return err!(CustomError::AssetAccountsMismatch);
+                };
+
+                anchor_lang::system_program::transfer(
+                    CpiContext::new(
+                        ctx.accounts.system_program.key(),
+                        anchor_lang::system_program::Transfer {
+                            from: ctx.accounts.created_by.to_account_info(),
+                            to: sol_vault.to_account_info(),
+                        },
+                    ),
+                    subsidy_amount,
+                )?;
+            }
+        }
+    }
+
+    let creator = ctx.accounts.created_by.key();
+    let bond_amount = if protocol.has_role(creator, ctx.accounts.roles.as_deref(), ROLE_POOL_CREATOR) {
+        0
+    } else {
+        protocol.creation_bond
+    };
+
+    if bond_amount > 0 {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.key(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.created_by.to_account_info(),
+                    to: ctx.accounts.creator_bond.to_account_info(),
+                },
+            ),
+            bond_amount,
+        )?;
+    }
+
+    let creator_bond = &mut ctx.accounts.creator_bond;
+    creator_bond.pool = pool.key();
+    creator_bond.creator = creator;
+    creator_bond.amount = bond_amount;
+    creator_bond.bump = ctx.bumps.creator_bond;
+
+    emit!(CreatorBondPosted {
+        pool: pool.key(),
+        creator,
+        amount: bond_amount,
+    });
+
     emit!(PoolCreated {
         pool_name: title,
         start_time,
         end_time,
+        subsidy_amount: pool.subsidy_amount,
+        fee_bps: pool.fee_bps,
     });
 
     Ok(())
@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{Pool, PoolStatus, ResolutionProposal};
+use crate::constants::{SEED_BOND_VAULT, SEED_POOL, SEED_PROPOSAL};
+use crate::errors::CustomError;
+use crate::events::OutcomeDisputed;
+
+#[derive(Accounts)]
+pub struct DisputeOutcome<'info> {
+    #[account(mut)]
+    pub disputer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_POOL, pool.created_by.as_ref(), &(pool.pool_id.to_le_bytes())],
+        bump = pool.bump
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_PROPOSAL, pool.key().as_ref()],
+        bump = proposal.bump,
+    )]
+    pub proposal: Box<Account<'info, ResolutionProposal>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_BOND_VAULT, pool.key().as_ref()],
+        bump,
+        token::authority = pool,
+    )]
+    pub bond_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = pool.stake_token_mint
+    )]
+    pub disputer_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = pool.stake_token_mint @ CustomError::PoolMismatch)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Challenges a pending proposal by posting a bond equal to the proposer's,
+/// escalating the pool to the protocol arbiter.
+pub fn dispute_outcome(ctx: Context<DisputeOutcome>) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let proposal = &mut ctx.accounts.proposal;
+    let clock = Clock::get()?;
+
+    require!(pool.status == PoolStatus::Proposed, CustomError::InvalidProposalState);
+    require!(
+        clock.unix_timestamp < proposal.dispute_deadline,
+        CustomError::DisputeWindowClosed
+    );
+
+    let mut bond_amount = proposal.bond_amount;
+
+    if bond_amount > 0 {
+        let vault_before = ctx.accounts.bond_vault.amount;
+
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.key(),
+                TransferChecked {
+                    from: ctx.accounts.disputer_token_account.to_account_info(),
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    to: ctx.accounts.bond_vault.to_account_info(),
+                    authority: ctx.accounts.disputer.to_account_info(),
+                },
+            ),
+            bond_amount,
+            ctx.accounts.token_mint.decimals,
+        )?;
+
+        // Arbitration pays back whatever the vault holds beyond the proposer's bond.
+        ctx.accounts.bond_vault.reload()?;
+        bond_amount = ctx
+            .accounts
+            .bond_vault
+            .amount
+            .checked_sub(vault_before)
+            .ok_or(CustomError::MathOverflow)?;
+    }
+
+    proposal.disputer = ctx.accounts.disputer.key();
+    proposal.disputed_at = clock.unix_timestamp;
+    pool.status = PoolStatus::Disputed;
+
+    emit!(OutcomeDisputed {
+        pool: pool.key(),
+        disputer: proposal.disputer,
+        bond_amount,
+    });
+
+    msg!("Proposal for pool {} disputed. Awaiting arbiter.", pool.pool_id);
+
+    Ok(())
+}
@@ -1,9 +1,15 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
-use crate::state::{Bet, Pool, PoolStatus, BetStatus};
-use crate::constants::{SEED_POOL, SEED_POOL_VAULT};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::{
+    Bet, Pool, PoolStatus, BetStatus, Protocol, ResolutionProposal, SolVault, StakeReceipt, VoidReason,
+};
+use crate::constants::{
+    SEED_BOND_VAULT, SEED_POOL, SEED_POOL_VAULT, SEED_PROPOSAL, SEED_PROTOCOL, SEED_SOL_VAULT,
+    SEED_STAKE_RECEIPT,
+};
 use crate::errors::CustomError;
-use crate::events::BetRefunded;
+use crate::events::{BetRefunded, PoolVoided, SubsidyReturned};
+use crate::utils::{DisputeBonds, PoolVault};
 
 const REFUND_TIMEOUT_SECONDS: i64 = 60;
 
@@ -12,12 +18,20 @@ pub struct EmergencyRefund<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
 
+    /// Gas sponsor that paid the bet rent at init_bet; receives it back when the bet closes.
     #[account(
         mut,
-        constraint = sponsor.key() == pool.created_by @ CustomError::Unauthorized
+        constraint = sponsor.key() == bet.sponsor @ CustomError::Unauthorized
     )]
     pub sponsor: Signer<'info>,
 
+    #[account(seeds = [SEED_PROTOCOL], bump)]
+    pub protocol: Box<Account<'info, Protocol>>,
+
+    /// CHECK: Paid for the pool and vault at create_pool; receives their rent once the last bet closes.
+    #[account(mut, address = pool.created_by @ CustomError::Unauthorized)]
+    pub pool_creator: UncheckedAccount<'info>,
+
     #[account(
         mut,
         close = sponsor,
@@ -34,23 +48,91 @@ pub struct EmergencyRefund<'info> {
     )]
     pub pool: Box<Account<'info, Pool>>,
 
+    /// Token pools only.
     #[account(
         mut,
         seeds = [SEED_POOL_VAULT, pool.key().as_ref()],
         bump,
         token::authority = pool,
     )]
-    pub pool_vault: Box<Account<'info, TokenAccount>>,
+    pub pool_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Native SOL pools only.
+    #[account(
+        mut,
+        seeds = [SEED_SOL_VAULT, pool.key().as_ref()],
+        bump = sol_vault.bump
+    )]
+    pub sol_vault: Option<Box<Account<'info, SolVault>>>,
 
+    /// Token pools only; native pools refund the user's wallet.
     #[account(
         mut,
         token::mint = pool.stake_token_mint
     )]
-    pub user_token_account: Box<Account<'info, TokenAccount>>,
+    pub user_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Token pools only. Receives any creator subsidy left behind once the last bet
+    /// is refunded; native pools return it to `pool_creator`.
+    #[account(
+        mut,
+        token::mint = pool.stake_token_mint,
+        token::authority = pool.created_by,
+    )]
+    pub creator_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// The bet's top-up receipt, if `add_stake` was ever used. Closed to the user;
+    /// any deposit never credited by `update_bet` is refunded on void/emergency paths.
+    #[account(
+        mut,
+        close = user,
+        seeds = [SEED_STAKE_RECEIPT, bet.key().as_ref()],
+        bump = stake_receipt.bump,
+        constraint = stake_receipt.bet == bet.key() @ CustomError::StakeReceiptMismatch
+    )]
+    pub stake_receipt: Option<Box<Account<'info, StakeReceipt>>>,
+
+    /// Disputed pools only: the proposal the arbiter never settled. Closed to its
+    /// proposer once the dispute times out.
+    #[account(
+        mut,
+        seeds = [SEED_PROPOSAL, pool.key().as_ref()],
+        bump = proposal.bump,
+    )]
+    pub proposal: Option<Box<Account<'info, ResolutionProposal>>>,
+
+    /// Disputed pools only.
+    #[account(
+        mut,
+        seeds = [SEED_BOND_VAULT, pool.key().as_ref()],
+        bump,
+        token::authority = pool,
+    )]
+    pub bond_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 
-    pub token_program: Program<'info, Token>,
+    /// CHECK: Disputed pools only. Receives the proposal and bond vault rent;
+    /// validated against the proposal in the handler.
+    #[account(mut)]
+    pub proposer: Option<UncheckedAccount<'info>>,
+
+    /// Disputed pools only; owner validated against the proposal in the handler.
+    #[account(mut, token::mint = pool.stake_token_mint)]
+    pub proposer_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Disputed pools only; owner validated against the proposal in the handler.
+    #[account(mut, token::mint = pool.stake_token_mint)]
+    pub disputer_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Token pools only; writable so transfer fees withheld on the vault can be
+    /// harvested before it closes.
+    #[account(mut, address = pool.stake_token_mint @ CustomError::PoolMismatch)]
+    pub token_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
 }
 
+/// Refunds a bet whose pool was never resolved. A dispute the arbiter has not
+/// settled within `DISPUTE_TIMEOUT` voids the pool first, returning both bonds.
 pub fn emergency_refund(ctx: Context<EmergencyRefund>) -> Result<()> {
     let bet = &mut ctx.accounts.bet;
     let pool = &mut ctx.accounts.pool;
@@ -63,7 +145,66 @@ pub fn emergency_refund(ctx: Context<EmergencyRefund>) -> Result<()> {
         CustomError::AlreadyResolved
     );
 
-    if pool.status != PoolStatus::Cancelled {
+    let vault = PoolVault::resolve(
+        pool,
+        ctx.accounts.pool_vault.as_deref(),
+        ctx.accounts.sol_vault.as_deref(),
+        ctx.accounts.token_mint.as_deref(),
+        ctx.accounts.token_program.as_ref(),
+    )?;
+
+    // Anyone can settle an undisputed proposal once its window closes.
+    require!(pool.status != PoolStatus::Proposed, CustomError::InvalidProposalState);
+
+    if pool.status == PoolStatus::Disputed {
+        let bonds = DisputeBonds::resolve(
+            ctx.accounts.proposal.as_deref(),
+            ctx.accounts.bond_vault.as_deref(),
+            ctx.accounts.proposer.as_ref().map(|account| account.to_account_info()),
+            ctx.accounts.proposer_token_account.as_deref(),
+            ctx.accounts.disputer_token_account.as_deref(),
+            clock.unix_timestamp,
+        )?;
+        bonds.refund(
+            pool,
+            ctx.accounts.token_mint.as_deref().ok_or(CustomError::DisputeAccountsMissing)?,
+            ctx.accounts.token_program.as_ref().ok_or(CustomError::DisputeAccountsMissing)?,
+        )?;
+
+        pool.void(VoidReason::DisputeTimedOut, clock.unix_timestamp);
+
+        // As in void_pool, the subsidy goes back up front so voided claims see only stakes.
+        let subsidy = pool.subsidy_amount;
+        if subsidy > 0 {
+            vault.pay(
+                pool,
+                ctx.accounts.creator_token_account.as_ref().map(|account| account.to_account_info()),
+                Some(ctx.accounts.pool_creator.to_account_info()),
+                subsidy,
+            )?;
+            pool.subsidy_amount = 0;
+
+            emit!(SubsidyReturned {
+                pool: pool.key(),
+                creator: pool.created_by,
+                amount: subsidy,
+            });
+        }
+
+        emit!(PoolVoided {
+            pool: pool.key(),
+            reason: VoidReason::DisputeTimedOut,
+            total_staked: pool.total_staked,
+            voided_at: clock.unix_timestamp,
+        });
+    }
+
+    require!(
+        !pool.claim_deadline_passed(clock.unix_timestamp),
+        CustomError::ClaimDeadlinePassed
+    );
+
+    if pool.status != PoolStatus::Cancelled && pool.status != PoolStatus::Voided {
         require!(
             clock.unix_timestamp > bet.end_timestamp + REFUND_TIMEOUT_SECONDS,
             CustomError::TimeoutNotMet
@@ -71,32 +212,35 @@ pub fn emergency_refund(ctx: Context<EmergencyRefund>) -> Result<()> {
     }
 
     let refund_amount = if pool.total_participants == 1 {
-        ctx.accounts.pool_vault.amount
+        vault
+            .balance()?
+            .checked_sub(pool.subsidy_amount)
+            .ok_or(CustomError::MathOverflow)?
     } else {
-        bet.stake
+        let uncredited = ctx
+            .accounts
+            .stake_receipt
+            .as_ref()
+            .map_or(0, |receipt| receipt.pending(bet.credited_top_ups));
+        bet.stake.checked_add(uncredited).ok_or(CustomError::MathOverflow)?
     };
 
     if refund_amount > 0 {
-        let created_by_bytes = pool.created_by.as_ref();
-        let pool_id_bytes = pool.pool_id.to_le_bytes();
-        let bump = pool.bump;
-        let seeds = &[SEED_POOL, created_by_bytes, &pool_id_bytes, &[bump]];
-        let signer = &[&seeds[..]];
-
-       token::transfer(
-            CpiContext::new_with_signer(
-                ctx.accounts.token_program.key(),
-                Transfer {
-                    from: ctx.accounts.pool_vault.to_account_info(),
-                    to: ctx.accounts.user_token_account.to_account_info(),
-                    authority: pool.to_account_info(),
-                },
-                signer,
-            ),
+        vault.pay(
+            pool,
+            ctx.accounts.user_token_account.as_ref().map(|account| account.to_account_info()),
+            Some(ctx.accounts.user.to_account_info()),
             refund_amount,
         )?;
 
-        pool.total_staked = pool.total_staked.checked_sub(refund_amount).unwrap();
+        pool.total_staked = pool.total_staked
+            .checked_sub(refund_amount)
+            .ok_or(CustomError::MathOverflow)?;
+    }
+
+    // The bet will never be scored, so none of its deposits remain pending.
+    if let Some(receipt) = ctx.accounts.stake_receipt.as_ref() {
+        pool.pending_top_ups = pool.pending_top_ups.saturating_sub(receipt.total_amount);
     }
 
     if pool.status != PoolStatus::Resolving
@@ -118,32 +262,35 @@ pub fn emergency_refund(ctx: Context<EmergencyRefund>) -> Result<()> {
     msg!("Emergency Refund executed for user: {}", ctx.accounts.user.key());
 
     if pool.total_participants == 0 {
-        let created_by_bytes = pool.created_by.as_ref();
-        let pool_id_bytes = pool.pool_id.to_le_bytes();
-        let bump = pool.bump;
-        let seeds = &[SEED_POOL, created_by_bytes, &pool_id_bytes, &[bump]];
-        let signer = &[&seeds[..]];
-
-        token::close_account(
-            CpiContext::new_with_signer(
-                ctx.accounts.token_program.key(),
-                token::CloseAccount {
-                    account: ctx.accounts.pool_vault.to_account_info(),
-                    destination: ctx.accounts.sponsor.to_account_info(),
-                    authority: pool.to_account_info(),
-                },
-                signer,
-            ),
-        )?;
+        let subsidy = pool.subsidy_amount;
+        if subsidy > 0 {
+            vault.pay(
+                pool,
+                ctx.accounts.creator_token_account.as_ref().map(|account| account.to_account_info()),
+                Some(ctx.accounts.pool_creator.to_account_info()),
+                subsidy,
+            )?;
+
+            emit!(SubsidyReturned {
+                pool: pool.key(),
+                creator: pool.created_by,
+                amount: subsidy,
+            });
+        }
+
+        vault.close(pool, ctx.accounts.pool_creator.to_account_info())?;
 
         let pool_info = pool.to_account_info();
-        let sponsor_info = ctx.accounts.sponsor.to_account_info();
+        let creator_info = ctx.accounts.pool_creator.to_account_info();
         
-        let sponsor_lamports = sponsor_info.lamports();
+        let creator_lamports = creator_info.lamports();
         let pool_lamports = pool_info.lamports();
         
-        **sponsor_info.lamports.borrow_mut() = sponsor_lamports.checked_add(pool_lamports).unwrap();
+        **creator_info.lamports.borrow_mut() = creator_lamports
+            .checked_add(pool_lamports)
+            .ok_or(CustomError::MathOverflow)?;
         **pool_info.lamports.borrow_mut() = 0;
+        pool.subsidy_amount = 0;
 
         msg!("All refunds completed. Pool and vault accounts closed, rent reclaimed.");
     }
@@ -1,8 +1,10 @@
-use crate::constants::{SEED_POOL, SEED_POOL_VAULT, SEED_PROTOCOL};
+use crate::constants::{SEED_POOL, SEED_POOL_VAULT, SEED_PROTOCOL, SEED_SOL_VAULT};
 use crate::errors::CustomError;
-use crate::state::{Pool, PoolStatus, Protocol};
+use crate::state::{Pool, PoolStatus, Protocol, SolVault};
+use crate::events::{PoolFinalized, SubsidyReturned};
+use crate::utils::PoolVault;
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 #[derive(Accounts)]
 pub struct FinalizeWeights<'info> {
@@ -22,18 +24,48 @@ pub struct FinalizeWeights<'info> {
     )]
     pub pool: Account<'info, Pool>,
 
+    /// Token pools only.
     #[account(
         mut,
         seeds = [SEED_POOL_VAULT, pool.key().as_ref()],
         bump,
         token::authority = pool,
     )]
-    pub pool_vault: Account<'info, TokenAccount>,
+    pub pool_vault: Option<InterfaceAccount<'info, TokenAccount>>,
 
+    /// Native SOL pools only.
+    #[account(
+        mut,
+        seeds = [SEED_SOL_VAULT, pool.key().as_ref()],
+        bump = sol_vault.bump
+    )]
+    pub sol_vault: Option<Account<'info, SolVault>>,
+
+    /// Token pools only.
     #[account(mut)]
-    pub treasury_token_account: Account<'info, TokenAccount>,
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Receives the treasury fee of native SOL pools.
+    #[account(mut, address = protocol.treasury_wallet @ CustomError::Unauthorized)]
+    pub treasury_wallet: Option<UncheckedAccount<'info>>,
+
+    /// Token pools only.
+    #[account(
+        mut,
+        token::mint = pool.stake_token_mint,
+        token::authority = pool.created_by,
+    )]
+    pub creator_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Receives the creator fee and returned subsidy of native SOL pools.
+    #[account(mut, address = pool.created_by @ CustomError::Unauthorized)]
+    pub pool_creator: Option<UncheckedAccount<'info>>,
+
+    /// Token pools only.
+    #[account(address = pool.stake_token_mint @ CustomError::PoolMismatch)]
+    pub token_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
 }
 
 pub fn finalize_weights(ctx: Context<FinalizeWeights>) -> Result<()> {
@@ -58,42 +90,100 @@ pub fn finalize_weights(ctx: Context<FinalizeWeights>) -> Result<()> {
         pool.total_weight = 0;
     }
 
-    let total_assets = ctx.accounts.pool_vault.amount;
-    let mut distributable_amount = total_assets;
-
-    if config.protocol_fee_bps > 0 && pool.total_participants > 1 && pool.total_weight > 0 {
-        let fee_amount = (total_assets as u128)
-            .checked_mul(config.protocol_fee_bps as u128)
+    let vault = PoolVault::resolve(
+        pool,
+        ctx.accounts.pool_vault.as_ref(),
+        ctx.accounts.sol_vault.as_ref(),
+        ctx.accounts.token_mint.as_deref(),
+        ctx.accounts.token_program.as_ref(),
+    )?;
+    let treasury_token_account = ctx.accounts.treasury_token_account.as_ref().map(|account| account.to_account_info());
+    let treasury_wallet = ctx.accounts.treasury_wallet.as_ref().map(|account| account.to_account_info());
+    let creator_token_account = ctx.accounts.creator_token_account.as_ref().map(|account| account.to_account_info());
+    let creator_wallet = ctx.accounts.pool_creator.as_ref().map(|account| account.to_account_info());
+
+    let total_assets = vault.balance()?;
+    let subsidy = pool.subsidy_amount;
+    // Fees are charged on participant stakes only, never on the creator's subsidy.
+    let staked_assets = total_assets.checked_sub(subsidy).unwrap();
+    let mut distributable_amount = staked_assets;
+    let mut protocol_fee: u64 = 0;
+    let mut creator_fee: u64 = 0;
+    let mut referral_reserve: u64 = 0;
+    let mut subsidy_distributed: u64 = 0;
+
+    // Fees are only taken when there is a real winner set; refund-only pools pay none.
+    if pool.total_participants > 1 && pool.total_weight > 0 {
+        // Every bet has been scored, so the pending top-ups are exactly the uncredited
+        // deposits; they are refunded at claim and never charged or distributed.
+        let staked_assets = staked_assets.checked_sub(pool.pending_top_ups).unwrap();
+
+        protocol_fee = (staked_assets as u128)
+            .checked_mul(pool.fee_bps as u128)
             .unwrap()
             .checked_div(10000)
             .unwrap() as u64;
 
-        if fee_amount > 0 {
-            let created_by_bytes = pool.created_by.as_ref();
-            let pool_id_bytes = pool.pool_id.to_le_bytes();
-            let bump = pool.bump;
-            let seeds = &[SEED_POOL, created_by_bytes, &pool_id_bytes, &[bump]];
-            let signer = &[&seeds[..]];
-
-            token::transfer(
-                CpiContext::new_with_signer(
-                    ctx.accounts.token_program.key(),
-                    Transfer {
-                        from: ctx.accounts.pool_vault.to_account_info(),
-                        to: ctx.accounts.treasury_token_account.to_account_info(),
-                        authority: pool.to_account_info(),
-                    },
-                    signer,
-                ),
-                fee_amount,
-            )?;
-
-            distributable_amount = total_assets.checked_sub(fee_amount).unwrap();
+        // Referrers earn `referral_fee_bps` of the protocol fee on the stake they brought
+        // in. That share stays in the vault and is paid out as referred bets are claimed.
+        if pool.referred_stake > 0 && pool.total_staked > 0 {
+            referral_reserve = (protocol_fee as u128)
+                .checked_mul(config.referral_fee_bps as u128)
+                .unwrap()
+                .checked_div(10000)
+                .unwrap()
+                .checked_mul(pool.referred_stake.min(pool.total_staked) as u128)
+                .unwrap()
+                .checked_div(pool.total_staked as u128)
+                .unwrap() as u64;
         }
+
+        creator_fee = (staked_assets as u128)
+            .checked_mul(pool.creator_fee_bps as u128)
+            .unwrap()
+            .checked_div(10000)
+            .unwrap() as u64;
+
+        let treasury_fee = protocol_fee.checked_sub(referral_reserve).unwrap();
+
+        vault.pay(pool, treasury_token_account, treasury_wallet, treasury_fee)?;
+        vault.pay(pool, creator_token_account, creator_wallet, creator_fee)?;
+
+        subsidy_distributed = subsidy;
+        distributable_amount = total_assets
+            .checked_sub(pool.pending_top_ups)
+            .unwrap()
+            .checked_sub(protocol_fee)
+            .unwrap()
+            .checked_sub(creator_fee)
+            .unwrap();
+    } else if subsidy > 0 {
+        // Nobody scored: the subsidy goes back to the creator instead of being
+        // swept by whoever claims last.
+        vault.pay(pool, creator_token_account, creator_wallet, subsidy)?;
+
+        emit!(SubsidyReturned {
+            pool: pool.key(),
+            creator: pool.created_by,
+            amount: subsidy,
+        });
     }
 
     pool.distributable_amount = distributable_amount;
+    pool.referral_reserve = referral_reserve;
+    pool.subsidy_amount = 0;
     pool.status = PoolStatus::Resolved;
+    pool.open_claim_window(clock.unix_timestamp);
+
+    emit!(PoolFinalized {
+        pool: pool.key(),
+        total_assets,
+        protocol_fee,
+        creator_fee,
+        referral_reserve,
+        subsidy_distributed,
+        distributable_amount,
+    });
 
     Ok(())
 }
@@ -1,26 +1,32 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
-use crate::state::{Protocol, Pool, Bet, BetStatus};
-use crate::constants::{SEED_BET, SEED_POOL, SEED_POOL_VAULT, SEED_PROTOCOL};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{AssetKind, Protocol, Pool, Bet, BetStatus, Roles, SolVault};
+use crate::constants::{
+    ROLE_SPONSOR, SEED_BET, SEED_POOL, SEED_POOL_VAULT, SEED_PROTOCOL, SEED_ROLES, SEED_SOL_VAULT,
+};
 use crate::errors::CustomError;
 use ephemeral_rollups_sdk::ephemeral_accounts::rent;
 
 #[derive(Accounts)]
-#[instruction(amount: u64, request_id: String)]
+#[instruction(amount: u64, request_id: String, referrer: Option<Pubkey>)]
 pub struct InitBet<'info> {
-    /// The user who owns the bet and authorizes the USDC transfer.
+    /// The user who owns the bet and authorizes the stake transfer.
     /// Does NOT pay for account creation — sponsor covers rent.
     #[account(mut)]
     pub user: Signer<'info>,
 
-    /// The protocol authority (sponsor) that pays for the bet account rent.
-    /// Constrained to protocol.admin so only the authorized sponsor can be used.
+    /// The gas sponsor that pays for the bet account rent: the admin or a
+    /// holder of `ROLE_SPONSOR`.
     #[account(
         mut,
-        constraint = sponsor.key() == protocol.admin @ CustomError::Unauthorized
+        constraint = protocol.has_role(sponsor.key(), roles.as_deref(), ROLE_SPONSOR) @ CustomError::Unauthorized
     )]
     pub sponsor: Signer<'info>,
 
+    /// Roles PDA of `sponsor`; omit when `sponsor` is the protocol admin.
+    #[account(seeds = [SEED_ROLES, sponsor.key().as_ref()], bump = roles.bump)]
+    pub roles: Option<Account<'info, Roles>>,
+
     #[account(
         seeds = [SEED_PROTOCOL],
         bump,
@@ -35,16 +41,26 @@ pub struct InitBet<'info> {
     )]
     pub pool: Box<Account<'info, Pool>>,
 
+    /// Token pools only.
     #[account(
         mut,
         seeds = [SEED_POOL_VAULT, pool.key().as_ref()],
         bump,
         token::authority = pool,
     )]
-    pub pool_vault: Box<Account<'info, TokenAccount>>,
+    pub pool_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 
+    /// Native SOL pools only.
+    #[account(
+        mut,
+        seeds = [SEED_SOL_VAULT, pool.key().as_ref()],
+        bump = sol_vault.bump
+    )]
+    pub sol_vault: Option<Box<Account<'info, SolVault>>>,
+
+    /// Token pools only.
     #[account(mut)]
-    pub user_token_account: Box<Account<'info, TokenAccount>>,
+    pub user_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 
     #[account(
         init,
@@ -55,7 +71,11 @@ pub struct InitBet<'info> {
     )]
     pub bet: Box<Account<'info, Bet>>,
 
-    pub token_program: Program<'info, Token>,
+    /// Token pools only.
+    #[account(address = pool.stake_token_mint @ CustomError::PoolMismatch)]
+    pub token_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
@@ -64,33 +84,84 @@ pub fn init_bet(
     ctx: Context<InitBet>,
     amount: u64,
     _request_id: String,
+    referrer: Option<Pubkey>,
 ) -> Result<()> {
     let pool_key = ctx.accounts.pool.key();
-    let pool = &mut ctx.accounts.pool;
     let clock = Clock::get()?;
 
-    require!(clock.unix_timestamp >= pool.start_time, CustomError::DurationTooShort);
-    require!(clock.unix_timestamp < pool.cutoff_time, CustomError::MarketClosed); 
+    require!(clock.unix_timestamp >= ctx.accounts.pool.start_time, CustomError::DurationTooShort);
+    require!(clock.unix_timestamp < ctx.accounts.pool.cutoff_time, CustomError::MarketClosed); 
+
+    let referrer = referrer.unwrap_or_default();
+    require!(referrer != ctx.accounts.user.key(), CustomError::InvalidReferrer);
+
+    let received = match ctx.accounts.pool.asset_kind {
+        AssetKind::Token => {
+            let (Some(pool_vault), Some(user_token_account), Some(token_mint), Some(token_program)) = (
+                ctx.accounts.pool_vault.as_mut(),
+                ctx.accounts.user_token_account.as_ref(),
+                ctx.accounts.token_mint.as_ref(),
+                ctx.accounts.token_program.as_ref(),
+            ) else {
+                return err!(CustomError::AssetAccountsMismatch);
+            };
+
+            let vault_before = pool_vault.amount;
+
+            token_interface::transfer_checked(
+                CpiContext::new(
+                    token_program.key(),
+                    TransferChecked {
+                        from: user_token_account.to_account_info(),
+                        mint: token_mint.to_account_info(),
+                        to: pool_vault.to_account_info(),
+                        authority: ctx.accounts.user.to_account_info(),
+                    },
+                ),
+                amount,
+                token_mint.decimals,
+            )?;
+
+            // Transfer-fee mints deliver less than `amount`; only what reached the vault is staked.
+            pool_vault.reload()?;
+            pool_vault.amount.checked_sub(vault_before).unwrap()
+        }
+        AssetKind::Native => {
+            // Referral shares are paid into per-mint token vaults.
+            require!(referrer == Pubkey::default(), CustomError::UnsupportedAssetKind);
+            let Some(sol_vault) = ctx.accounts.sol_vault.as_ref() else {
+                return err!(CustomError::AssetAccountsMismatch);
+            };
+
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.key(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.user.to_account_info(),
+                        to: sol_vault.to_account_info(),
+                    },
+                ),
+                amount,
+            )?;
+            amount
+        }
+    };
 
-    token::transfer(
-        CpiContext::new(
-            ctx.accounts.token_program.key(),
-            Transfer {
-                from: ctx.accounts.user_token_account.to_account_info(),
-                to: ctx.accounts.pool_vault.to_account_info(),
-                authority: ctx.accounts.user.to_account_info(),
-            },
-        ),
-        amount,
-    )?;
+    let pool = &mut ctx.accounts.pool;
+
+    pool.check_bet_stake(received, received)?;
+    pool.check_pool_capacity(received)?;
 
-    pool.total_staked = pool.total_staked.checked_add(amount).unwrap();
+    pool.total_staked = pool.total_staked.checked_add(received).unwrap();
     pool.total_participants = pool.total_participants.checked_add(1).unwrap();
 
+    let referred_amount = if referrer == Pubkey::default() { 0 } else { received };
+    pool.referred_stake = pool.referred_stake.checked_add(referred_amount).unwrap();
+
     let bet = &mut ctx.accounts.bet;
     bet.user_pubkey = ctx.accounts.user.key();
     bet.pool_pubkey = pool_key;
-    bet.stake = amount; 
+    bet.stake = received; 
     bet.end_timestamp = pool.end_time;
     bet.creation_ts = clock.unix_timestamp; 
     bet.update_count = 0;                   
@@ -100,6 +171,15 @@ pub fn init_bet(
     bet.status = BetStatus::Active;
     bet.prediction = 0; 
     bet.bump = ctx.bumps.bet;
+    bet.scored_error = 0;
+    bet.bonus_entry_ts = clock.unix_timestamp;
+    bet.prediction_high = 0;
+    bet.referrer = referrer;
+    bet.referred_amount = referred_amount;
+    bet.credited_top_ups = 0;
+    bet.receipt_nonce = 0;
+    bet.sponsor = ctx.accounts.sponsor.key();
+    bet.version = Bet::VERSION;
 
     anchor_lang::system_program::transfer(
         CpiContext::new(
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::ReferralStats;
+use crate::constants::{SEED_REFERRAL, SEED_REFERRAL_VAULT};
+
+#[derive(Accounts)]
+pub struct InitReferralStats<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Any wallet can be a referrer; it only needs to sign when claiming.
+    pub referrer: UncheckedAccount<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = ReferralStats::SPACE,
+        seeds = [SEED_REFERRAL, referrer.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub referral_stats: Account<'info, ReferralStats>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [SEED_REFERRAL_VAULT, referral_stats.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = referral_stats,
+    )]
+    pub referral_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Permissionless: opens the ledger and vault a referrer is paid into for one mint.
+/// Must exist before a referred bet in a pool of that mint is claimed.
+pub fn init_referral_stats(ctx: Context<InitReferralStats>) -> Result<()> {
+    let stats = &mut ctx.accounts.referral_stats;
+    stats.referrer = ctx.accounts.referrer.key();
+    stats.mint = ctx.accounts.mint.key();
+    stats.total_earned = 0;
+    stats.total_claimed = 0;
+    stats.referred_bets = 0;
+    stats.bump = ctx.bumps.referral_stats;
+
+    Ok(())
+}
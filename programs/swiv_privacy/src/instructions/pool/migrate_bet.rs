@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use crate::state::{Bet, Protocol};
+use crate::constants::SEED_PROTOCOL;
+use crate::events::AccountMigrated;
+use crate::utils::{grow_account, upgrade_account};
+
+#[derive(Accounts)]
+pub struct MigrateBet<'info> {
+    /// Permissionless: the upgrade is deterministic. The payer covers any extra rent.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Run `migrate_protocol` first if the protocol itself is on an older layout.
+    #[account(seeds = [SEED_PROTOCOL], bump)]
+    pub protocol: Box<Account<'info, Protocol>>,
+
+    /// CHECK: Deserialized by hand because a bet on an older layout may be too short
+    /// for `Account<Bet>`; the discriminator is checked on read.
+    #[account(mut, owner = crate::ID)]
+    pub bet: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// L1 instruction: delegated bets are owned by the delegation program and must be
+/// committed back first.
+pub fn migrate_bet(ctx: Context<MigrateBet>) -> Result<()> {
+    let info = ctx.accounts.bet.to_account_info();
+    let old_len = info.data_len();
+    let admin = ctx.accounts.protocol.admin;
+
+    grow_account(
+        &info,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        Bet::SPACE,
+    )?;
+    upgrade_account::<Bet>(&mut info.try_borrow_mut_data()?, |bet| bet.upgrade(admin))?;
+
+    emit!(AccountMigrated {
+        account: info.key(),
+        old_len: old_len as u64,
+        new_len: info.data_len() as u64,
+        version: Bet::VERSION,
+    });
+
+    Ok(())
+}
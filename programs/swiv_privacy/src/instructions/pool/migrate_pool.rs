@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use crate::state::{Pool, Protocol};
+use crate::constants::SEED_PROTOCOL;
+use crate::events::AccountMigrated;
+use crate::utils::{grow_account, upgrade_account};
+
+#[derive(Accounts)]
+pub struct MigratePool<'info> {
+    /// Permissionless: the upgrade is deterministic. The payer covers any extra rent.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Run `migrate_protocol` first if the protocol itself is on an older layout.
+    #[account(seeds = [SEED_PROTOCOL], bump)]
+    pub protocol: Box<Account<'info, Protocol>>,
+
+    /// CHECK: Deserialized by hand because a pool on an older layout may be too short
+    /// for `Account<Pool>`; the discriminator is checked on read.
+    #[account(mut, owner = crate::ID)]
+    pub pool: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// L1 instruction: delegated pools are owned by the delegation program and must be
+/// committed back first.
+pub fn migrate_pool(ctx: Context<MigratePool>) -> Result<()> {
+    let info = ctx.accounts.pool.to_account_info();
+    let old_len = info.data_len();
+    // Unversioned pools read the live fee at finalize_weights; keep charging that one.
+    let protocol_fee_bps = ctx.accounts.protocol.protocol_fee_bps;
+
+    grow_account(
+        &info,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        Pool::SPACE,
+    )?;
+    upgrade_account::<Pool>(&mut info.try_borrow_mut_data()?, |pool| pool.upgrade(protocol_fee_bps))?;
+
+    emit!(AccountMigrated {
+        account: info.key(),
+        old_len: old_len as u64,
+        new_len: info.data_len() as u64,
+        version: Pool::VERSION,
+    });
+
+    Ok(())
+}
@@ -1,6 +1,7 @@
 pub mod create_pool;
 pub mod place_bet;
 pub mod resolve_pool;
+pub mod resolve_pool_from_oracle;
 pub mod finalize_weights;
 pub mod claim_reward;
 pub mod update_bet;
@@ -8,14 +9,43 @@ pub mod add_stake;
 pub mod init_bet;
 pub mod emergency_refund;
 pub mod cancel_pool;
+pub mod propose_outcome;
+pub mod dispute_outcome;
+pub mod settle_proposal;
+pub mod arbitrate_dispute;
+pub mod submit_report;
+pub mod void_pool;
+pub mod release_creator_bond;
+pub mod init_referral_stats;
+pub mod claim_referral_fees;
+pub mod withdraw_bet;
+pub mod sweep_unclaimed;
+pub mod close_orphaned_bet;
+pub mod migrate_pool;
+pub mod migrate_bet;
 
 pub use create_pool::*;
 pub use place_bet::*;
 pub use resolve_pool::*;
+pub use resolve_pool_from_oracle::*;
 pub use finalize_weights::*;
 pub use claim_reward::*;
 pub use update_bet::*;
 pub use add_stake::*;
 pub use init_bet::*;
 pub use emergency_refund::*;
-pub use cancel_pool::*;
\ No newline at end of file
+pub use cancel_pool::*;
+pub use propose_outcome::*;
+pub use dispute_outcome::*;
+pub use settle_proposal::*;
+pub use arbitrate_dispute::*;
+pub use submit_report::*;
+pub use void_pool::*;
+pub use release_creator_bond::*;
+pub use init_referral_stats::*;
+pub use claim_referral_fees::*;
+pub use withdraw_bet::*;
+pub use sweep_unclaimed::*;
+pub use close_orphaned_bet::*;
+pub use migrate_pool::*;
+pub use migrate_bet::*;
\ No newline at end of file
@@ -4,7 +4,7 @@ use crate::constants::{SEED_BET, SEED_POOL, SEED_PROTOCOL};
 use crate::errors::CustomError;
 
 #[derive(Accounts)]
-#[instruction(prediction: u64, request_id: String)]
+#[instruction(prediction: u64, prediction_high: Option<u64>, request_id: String)]
 pub struct PlaceBet<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
@@ -34,6 +34,7 @@ pub struct PlaceBet<'info> {
 pub fn place_bet(
     ctx: Context<PlaceBet>,
     prediction: u64, 
+    prediction_high: Option<u64>,
     _request_id: String,
 ) -> Result<()> {
     let bet = &mut ctx.accounts.bet;
@@ -46,9 +47,15 @@ pub fn place_bet(
     );
     require!(clock.unix_timestamp < pool.cutoff_time, CustomError::MarketClosed);
     require!(bet.status == BetStatus::Active, CustomError::BetAlreadyInitialized);
+    pool.validate_prediction(prediction, prediction_high)?;
 
     bet.prediction = prediction;
+    bet.prediction_high = prediction_high.unwrap_or(prediction);
     bet.update_count = bet.update_count.checked_add(1).unwrap();
 
+    if pool.reset_bonus_on_update {
+        bet.bonus_entry_ts = clock.unix_timestamp;
+    }
+
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,135 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{AssetKind, Pool, PoolStatus, Protocol, ResolutionProposal};
+use crate::constants::{SEED_BOND_VAULT, SEED_POOL, SEED_PROPOSAL, SEED_PROTOCOL};
+use crate::errors::CustomError;
+use crate::events::OutcomeProposed;
+
+#[derive(Accounts)]
+pub struct ProposeOutcome<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_PROTOCOL],
+        bump,
+        constraint = !protocol.paused @ CustomError::Paused
+    )]
+    pub protocol: Box<Account<'info, Protocol>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_POOL, pool.created_by.as_ref(), &(pool.pool_id.to_le_bytes())],
+        bump = pool.bump,
+        constraint = pool.resolver_committee == Pubkey::default() @ CustomError::CommitteeResolutionRequired,
+        // Proposal bonds are posted in the stake token.
+        constraint = pool.asset_kind == AssetKind::Token @ CustomError::UnsupportedAssetKind
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = ResolutionProposal::SPACE,
+        seeds = [SEED_PROPOSAL, pool.key().as_ref()],
+        bump
+    )]
+    pub proposal: Box<Account<'info, ResolutionProposal>>,
+
+    #[account(
+        init,
+        payer = proposer,
+        seeds = [SEED_BOND_VAULT, pool.key().as_ref()],
+        bump,
+        token::mint = token_mint,
+        token::authority = pool,
+    )]
+    pub bond_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = pool.stake_token_mint @ CustomError::PoolMismatch)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        token::mint = pool.stake_token_mint
+    )]
+    pub proposer_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Opens an optimistic resolution: the proposer escrows `protocol.resolution_bond`
+/// and the outcome becomes final if nobody disputes it within the dispute window.
+pub fn propose_outcome(ctx: Context<ProposeOutcome>, proposed_outcome: u64) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let protocol = &ctx.accounts.protocol;
+    let clock = Clock::get()?;
+
+    require!(
+        pool.status == PoolStatus::Upcoming
+            || pool.status == PoolStatus::Active
+            || pool.status == PoolStatus::Closed,
+        CustomError::InvalidProposalState
+    );
+    require!(clock.unix_timestamp >= pool.end_time, CustomError::DurationTooShort);
+    pool.validate_outcome(proposed_outcome)?;
+
+    // Without a bond, proposing and disputing are free and nothing deters griefing.
+    require!(protocol.resolution_bond > 0, CustomError::ResolutionBondNotSet);
+
+    let vault_before = ctx.accounts.bond_vault.amount;
+
+    token_interface::transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.key(),
+            TransferChecked {
+                from: ctx.accounts.proposer_token_account.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: ctx.accounts.bond_vault.to_account_info(),
+                authority: ctx.accounts.proposer.to_account_info(),
+            },
+        ),
+        protocol.resolution_bond,
+        ctx.accounts.token_mint.decimals,
+    )?;
+
+    // Transfer-fee mints deliver less than the bond; escrow only what arrived.
+    ctx.accounts.bond_vault.reload()?;
+    let bond_amount = ctx
+        .accounts
+        .bond_vault
+        .amount
+        .checked_sub(vault_before)
+        .ok_or(CustomError::MathOverflow)?;
+
+    let dispute_deadline = clock
+        .unix_timestamp
+        .checked_add(protocol.dispute_window)
+        .ok_or(CustomError::MathOverflow)?;
+
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.pool = pool.key();
+    proposal.proposer = ctx.accounts.proposer.key();
+    proposal.proposed_outcome = proposed_outcome;
+    proposal.proposed_at = clock.unix_timestamp;
+    proposal.dispute_deadline = dispute_deadline;
+    proposal.bond_amount = bond_amount;
+    proposal.disputer = Pubkey::default();
+    proposal.disputed_at = 0;
+    proposal.bump = ctx.bumps.proposal;
+
+    pool.status = PoolStatus::Proposed;
+
+    emit!(OutcomeProposed {
+        pool: pool.key(),
+        proposer: proposal.proposer,
+        proposed_outcome,
+        bond_amount,
+        dispute_deadline,
+    });
+
+    msg!("Outcome {} proposed. Dispute window closes at {}", proposed_outcome, dispute_deadline);
+
+    Ok(())
+}
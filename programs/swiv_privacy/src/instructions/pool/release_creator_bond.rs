@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use crate::state::{CreatorBond, Pool, PoolStatus};
+use crate::constants::{SEED_CREATOR_BOND, SEED_POOL};
+use crate::errors::CustomError;
+use crate::events::CreatorBondReleased;
+
+#[derive(Accounts)]
+pub struct ReleaseCreatorBond<'info> {
+    pub caller: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_POOL, pool.created_by.as_ref(), &(pool.pool_id.to_le_bytes())],
+        bump = pool.bump
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        mut,
+        close = creator,
+        seeds = [SEED_CREATOR_BOND, pool.key().as_ref()],
+        bump = creator_bond.bump,
+    )]
+    pub creator_bond: Box<Account<'info, CreatorBond>>,
+
+    /// CHECK: Receives the bond and rent; validated against the bond record.
+    #[account(mut, address = creator_bond.creator @ CustomError::Unauthorized)]
+    pub creator: UncheckedAccount<'info>,
+}
+
+/// Permissionless: returns the creator bond once the pool has been resolved and
+/// finalized. Must run before the last claim closes the pool; afterwards only the
+/// admin can settle the bond through `moderate_creator_bond`.
+pub fn release_creator_bond(ctx: Context<ReleaseCreatorBond>) -> Result<()> {
+    let pool = &ctx.accounts.pool;
+
+    require!(
+        pool.status == PoolStatus::Resolved || pool.status == PoolStatus::Settled,
+        CustomError::SettlementTooEarly
+    );
+
+    emit!(CreatorBondReleased {
+        pool: pool.key(),
+        creator: ctx.accounts.creator.key(),
+        amount: ctx.accounts.creator_bond.amount,
+    });
+
+    msg!("Creator bond released for pool {}", pool.pool_id);
+
+    Ok(())
+}
@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
-use crate::state::{Pool, PoolStatus, Protocol};
-use crate::constants::{SEED_PROTOCOL, SEED_POOL};
+use crate::state::{Pool, PoolStatus, Protocol, Roles};
+use crate::constants::{ROLE_RESOLVER, SEED_PROTOCOL, SEED_POOL, SEED_ROLES};
 use crate::errors::CustomError;
 
 #[derive(Accounts)]
@@ -11,34 +11,47 @@ pub struct ResolvePool<'info> {
     #[account(
         seeds = [SEED_PROTOCOL],
         bump,
-        constraint = protocol.admin == admin.key() @ CustomError::Unauthorized
+        constraint = protocol.has_role(admin.key(), roles.as_deref(), ROLE_RESOLVER) @ CustomError::Unauthorized
     )]
     pub protocol: Account<'info, Protocol>,
 
+    /// Roles PDA of `admin`; omit when `admin` is the protocol admin.
+    #[account(seeds = [SEED_ROLES, admin.key().as_ref()], bump = roles.bump)]
+    pub roles: Option<Account<'info, Roles>>,
+
+    /// Committee pools are resolved through `submit_report`, not by a resolver.
     #[account(
         mut,
         seeds = [SEED_POOL, pool.created_by.as_ref(), &(pool.pool_id.to_le_bytes())],
-        bump = pool.bump
+        bump = pool.bump,
+        constraint = pool.resolver_committee == Pubkey::default() @ CustomError::CommitteeResolutionRequired
     )]
     pub pool: Account<'info, Pool>,
 }
 
-/// `final_outcome == 0` is a valid, intentional value: calculate_accuracy_score
-/// treats result == 0 as "no winners", giving every bet a weight of 0 and
-/// causing claim_reward to refund each participant's full stake. Admins should
-/// use 0 to void a pool (e.g. bad/unavailable oracle data) and resolve normally otherwise.
+/// For categorical pools `final_outcome` is the winning outcome index; for signed
+/// pools it is an `i64` in two's complement and 0 is an ordinary result.
+///
+/// To abandon a pool (e.g. bad or unavailable data) use `void_pool` instead of
+/// resolving it to a sentinel value.
 pub fn resolve_pool(ctx: Context<ResolvePool>, final_outcome: u64) -> Result<()> {
     let pool = &mut ctx.accounts.pool;
 
     require!(
         pool.status != PoolStatus::Resolved
             && pool.status != PoolStatus::Settled
-            && pool.status != PoolStatus::Cancelled,
+            && pool.status != PoolStatus::Cancelled
+            && pool.status != PoolStatus::Voided,
         CustomError::AlreadyResolved
     );
+    require!(
+        pool.status != PoolStatus::Proposed && pool.status != PoolStatus::Disputed,
+        CustomError::InvalidProposalState
+    );
 
     let clock = Clock::get()?;
     require!(clock.unix_timestamp >= pool.end_time, CustomError::DurationTooShort);
+    pool.validate_outcome(final_outcome)?;
 
     pool.resolution_result = final_outcome;
     pool.resolution_ts = clock.unix_timestamp;
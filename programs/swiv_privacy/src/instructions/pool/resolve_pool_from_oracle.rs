@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use crate::state::{Pool, PoolKind, PoolStatus};
+use crate::constants::{PYTH_PROGRAM_ID, SEED_POOL};
+use crate::errors::CustomError;
+use crate::events::PoolResolvedFromOracle;
+use crate::utils::oracle::{oracle_price_to_outcome, parse_pyth_price};
+
+#[derive(Accounts)]
+pub struct ResolvePoolFromOracle<'info> {
+    /// Permissionless: anyone may crank resolution once the oracle has a valid price.
+    #[account(mut)]
+    pub resolver: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_POOL, pool.created_by.as_ref(), &(pool.pool_id.to_le_bytes())],
+        bump = pool.bump,
+        constraint = pool.oracle_feed != Pubkey::default() @ CustomError::OracleNotConfigured
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// CHECK: Must be the Pyth price account stored on the pool; parsed manually.
+    #[account(
+        address = pool.oracle_feed @ CustomError::InvalidOracleAccount,
+        owner = PYTH_PROGRAM_ID @ CustomError::InvalidOracleAccount
+    )]
+    pub price_feed: UncheckedAccount<'info>,
+}
+
+pub fn resolve_pool_from_oracle(ctx: Context<ResolvePoolFromOracle>) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+
+    require!(
+        pool.status != PoolStatus::Resolving
+            && pool.status != PoolStatus::Resolved
+            && pool.status != PoolStatus::Settled
+            && pool.status != PoolStatus::Cancelled
+            && pool.status != PoolStatus::Voided,
+        CustomError::AlreadyResolved
+    );
+    require!(
+        pool.status != PoolStatus::Proposed && pool.status != PoolStatus::Disputed,
+        CustomError::InvalidProposalState
+    );
+
+    let clock = Clock::get()?;
+    require!(clock.unix_timestamp >= pool.end_time, CustomError::DurationTooShort);
+
+    let oracle = {
+        let data = ctx.accounts.price_feed.try_borrow_data()?;
+        parse_pyth_price(&data)?
+    };
+
+    let final_outcome = oracle_price_to_outcome(
+        &oracle,
+        pool.oracle_exponent,
+        pool.end_time,
+        pool.oracle_max_staleness,
+    )?;
+
+    if pool.kind == PoolKind::SignedNumeric {
+        require!(final_outcome <= i64::MAX as u64, CustomError::MathOverflow);
+    }
+
+    pool.resolution_result = final_outcome;
+    pool.resolution_ts = clock.unix_timestamp;
+    pool.status = PoolStatus::Resolving;
+
+    emit!(PoolResolvedFromOracle {
+        pool: pool.key(),
+        outcome: final_outcome,
+        oracle_price: oracle.price,
+        oracle_conf: oracle.conf,
+        oracle_expo: oracle.expo,
+        publish_time: oracle.publish_time,
+    });
+
+    msg!("Pool Resolving from oracle. Outcome: {}", final_outcome);
+
+    Ok(())
+}
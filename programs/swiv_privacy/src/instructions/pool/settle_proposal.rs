@@ -0,0 +1,122 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{Pool, PoolStatus, ResolutionProposal};
+use crate::constants::{SEED_BOND_VAULT, SEED_POOL, SEED_PROPOSAL};
+use crate::errors::CustomError;
+use crate::events::ProposalSettled;
+use crate::utils::harvest_withheld_fees;
+
+#[derive(Accounts)]
+pub struct SettleProposal<'info> {
+    /// Permissionless: anyone may settle once the dispute window has elapsed.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_POOL, pool.created_by.as_ref(), &(pool.pool_id.to_le_bytes())],
+        bump = pool.bump
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        mut,
+        close = proposer,
+        seeds = [SEED_PROPOSAL, pool.key().as_ref()],
+        bump = proposal.bump,
+    )]
+    pub proposal: Box<Account<'info, ResolutionProposal>>,
+
+    /// CHECK: Receives the proposal and bond vault rent; validated against the proposal.
+    #[account(mut, address = proposal.proposer @ CustomError::Unauthorized)]
+    pub proposer: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_BOND_VAULT, pool.key().as_ref()],
+        bump,
+        token::authority = pool,
+    )]
+    pub bond_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = pool.stake_token_mint,
+        token::authority = proposal.proposer,
+    )]
+    pub proposer_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Writable so transfer fees withheld on the bond vault can be harvested before it closes.
+    #[account(mut, address = pool.stake_token_mint @ CustomError::PoolMismatch)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Finalizes an undisputed proposal: returns the proposer's bond and moves the
+/// pool to `Resolving` so weight calculation can begin.
+pub fn settle_proposal(ctx: Context<SettleProposal>) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let proposal = &ctx.accounts.proposal;
+    let clock = Clock::get()?;
+
+    require!(pool.status == PoolStatus::Proposed, CustomError::InvalidProposalState);
+    require!(
+        clock.unix_timestamp >= proposal.dispute_deadline,
+        CustomError::DisputeWindowOpen
+    );
+
+    let created_by_bytes = pool.created_by.as_ref();
+    let pool_id_bytes = pool.pool_id.to_le_bytes();
+    let bump = pool.bump;
+    let seeds = &[SEED_POOL, created_by_bytes, &pool_id_bytes, &[bump]];
+    let signer = &[&seeds[..]];
+
+    if ctx.accounts.bond_vault.amount > 0 {
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.key(),
+                TransferChecked {
+                    from: ctx.accounts.bond_vault.to_account_info(),
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    to: ctx.accounts.proposer_token_account.to_account_info(),
+                    authority: pool.to_account_info(),
+                },
+                signer,
+            ),
+            ctx.accounts.bond_vault.amount,
+            ctx.accounts.token_mint.decimals,
+        )?;
+    }
+
+    harvest_withheld_fees(
+        ctx.accounts.bond_vault.to_account_info(),
+        ctx.accounts.token_mint.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+    )?;
+    token_interface::close_account(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.key(),
+            token_interface::CloseAccount {
+                account: ctx.accounts.bond_vault.to_account_info(),
+                destination: ctx.accounts.proposer.to_account_info(),
+                authority: pool.to_account_info(),
+            },
+            signer,
+        ),
+    )?;
+
+    pool.resolution_result = proposal.proposed_outcome;
+    pool.resolution_ts = clock.unix_timestamp;
+    pool.status = PoolStatus::Resolving;
+
+    emit!(ProposalSettled {
+        pool: pool.key(),
+        proposer: proposal.proposer,
+        outcome: proposal.proposed_outcome,
+    });
+
+    msg!("Pool Resolving. Undisputed outcome: {}", proposal.proposed_outcome);
+
+    Ok(())
+}
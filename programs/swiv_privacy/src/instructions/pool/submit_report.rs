@@ -0,0 +1,114 @@
+use anchor_lang::prelude::*;
+use crate::state::{CommitteeReport, CommitteeReports, Pool, PoolStatus, ResolverCommittee};
+use crate::constants::{SEED_COMMITTEE_REPORTS, SEED_POOL};
+use crate::errors::CustomError;
+use crate::events::{CommitteeResolved, OutlierReportFlagged, ReportSubmitted};
+use crate::utils::math::{calculate_deviation_bps, calculate_median};
+
+#[derive(Accounts)]
+pub struct SubmitReport<'info> {
+    #[account(mut)]
+    pub reporter: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_POOL, pool.created_by.as_ref(), &(pool.pool_id.to_le_bytes())],
+        bump = pool.bump
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        address = pool.resolver_committee @ CustomError::CommitteeResolutionRequired,
+        constraint = committee.reporters.contains(&reporter.key()) @ CustomError::NotCommitteeReporter
+    )]
+    pub committee: Box<Account<'info, ResolverCommittee>>,
+
+    #[account(
+        init_if_needed,
+        payer = reporter,
+        space = CommitteeReports::SPACE,
+        seeds = [SEED_COMMITTEE_REPORTS, pool.key().as_ref()],
+        bump
+    )]
+    pub reports: Box<Account<'info, CommitteeReports>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Records one committee member's outcome. Once `threshold` reports exist the
+/// median becomes the pool's result and reports far from it are flagged.
+pub fn submit_report(ctx: Context<SubmitReport>, outcome: u64) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let committee = &ctx.accounts.committee;
+    let reports = &mut ctx.accounts.reports;
+    let reporter_key = ctx.accounts.reporter.key();
+    let clock = Clock::get()?;
+
+    require!(
+        pool.status == PoolStatus::Upcoming
+            || pool.status == PoolStatus::Active
+            || pool.status == PoolStatus::Closed,
+        CustomError::AlreadyResolved
+    );
+    require!(clock.unix_timestamp >= pool.end_time, CustomError::DurationTooShort);
+    // create_pool only allows committees on unsigned numeric pools, where every u64 is
+    // a valid outcome, so reports and their median need no outcome validation.
+
+    if reports.pool == Pubkey::default() {
+        reports.pool = pool.key();
+        reports.committee = committee.key();
+        reports.bump = ctx.bumps.reports;
+    }
+
+    require!(
+        !reports.reports.iter().any(|r| r.reporter == reporter_key),
+        CustomError::DuplicateReport
+    );
+
+    reports.reports.push(CommitteeReport {
+        reporter: reporter_key,
+        outcome,
+    });
+
+    emit!(ReportSubmitted {
+        pool: pool.key(),
+        reporter: reporter_key,
+        outcome,
+    });
+
+    if reports.reports.len() < committee.threshold as usize {
+        msg!("Report recorded ({}/{}).", reports.reports.len(), committee.threshold);
+        return Ok(());
+    }
+
+    let mut outcomes: Vec<u64> = reports.reports.iter().map(|r| r.outcome).collect();
+    let median_outcome = calculate_median(&mut outcomes)?;
+
+    for report in reports.reports.iter() {
+        let deviation_bps = calculate_deviation_bps(report.outcome, median_outcome)?;
+        if deviation_bps > committee.outlier_threshold_bps {
+            emit!(OutlierReportFlagged {
+                pool: pool.key(),
+                reporter: report.reporter,
+                outcome: report.outcome,
+                median_outcome,
+                deviation_bps,
+            });
+        }
+    }
+
+    pool.resolution_result = median_outcome;
+    pool.resolution_ts = clock.unix_timestamp;
+    pool.status = PoolStatus::Resolving;
+
+    emit!(CommitteeResolved {
+        pool: pool.key(),
+        committee: committee.key(),
+        median_outcome,
+        report_count: reports.reports.len() as u8,
+    });
+
+    msg!("Pool Resolving. Committee median outcome: {}", median_outcome);
+
+    Ok(())
+}
@@ -0,0 +1,201 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::{AssetKind, Pool, PoolStatus, Protocol, SolVault};
+use crate::constants::{SEED_POOL, SEED_POOL_VAULT, SEED_PROTOCOL, SEED_SOL_VAULT};
+use crate::errors::CustomError;
+use crate::events::UnclaimedSwept;
+use crate::utils::PoolVault;
+
+#[derive(Accounts)]
+pub struct SweepUnclaimed<'info> {
+    /// Anyone may sweep to the treasury; rolling into another pool is admin-only.
+    #[account(mut)]
+    pub caller: Signer<'info>,
+
+    #[account(seeds = [SEED_PROTOCOL], bump)]
+    pub protocol: Box<Account<'info, Protocol>>,
+
+    /// CHECK: Paid for the pool and vault at create_pool; receives their rent.
+    #[account(mut, address = pool.created_by @ CustomError::Unauthorized)]
+    pub pool_creator: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_POOL, pool.created_by.as_ref(), &(pool.pool_id.to_le_bytes())],
+        bump = pool.bump
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// Token pools only.
+    #[account(
+        mut,
+        seeds = [SEED_POOL_VAULT, pool.key().as_ref()],
+        bump,
+        token::authority = pool,
+    )]
+    pub pool_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Native SOL pools only.
+    #[account(
+        mut,
+        seeds = [SEED_SOL_VAULT, pool.key().as_ref()],
+        bump = sol_vault.bump
+    )]
+    pub sol_vault: Option<Box<Account<'info, SolVault>>>,
+
+    /// Token pools only.
+    #[account(
+        mut,
+        token::mint = pool.stake_token_mint,
+        token::authority = protocol.treasury_wallet,
+    )]
+    pub treasury_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// CHECK: Receives the sweep of native SOL pools.
+    #[account(mut, address = protocol.treasury_wallet @ CustomError::Unauthorized)]
+    pub treasury_wallet: Option<UncheckedAccount<'info>>,
+
+    /// Open pool that receives the leftover balance as subsidy instead of the treasury.
+    #[account(
+        mut,
+        seeds = [SEED_POOL, rollover_pool.created_by.as_ref(), &(rollover_pool.pool_id.to_le_bytes())],
+        bump = rollover_pool.bump
+    )]
+    pub rollover_pool: Option<Box<Account<'info, Pool>>>,
+
+    /// CHECK: The rollover pool's token vault or SOL vault; its address is derived
+    /// from `rollover_pool` in the handler.
+    #[account(mut)]
+    pub rollover_vault: Option<UncheckedAccount<'info>>,
+
+    /// Token pools only; writable so transfer fees withheld on the vault can be
+    /// harvested before it closes.
+    #[account(mut, address = pool.stake_token_mint @ CustomError::PoolMismatch)]
+    pub token_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+/// Closes out a claimable pool whose claim deadline has passed. Bets that never
+/// claimed forfeit their share of the vault; `close_orphaned_bet` returns their rent.
+pub fn sweep_unclaimed(ctx: Context<SweepUnclaimed>) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let clock = Clock::get()?;
+
+    require!(
+        (pool.status == PoolStatus::Resolved
+            || pool.status == PoolStatus::Voided
+            || pool.status == PoolStatus::Cancelled)
+            && pool.claim_deadline_passed(clock.unix_timestamp),
+        CustomError::ClaimDeadlineNotReached
+    );
+
+    let vault = PoolVault::resolve(
+        pool,
+        ctx.accounts.pool_vault.as_deref(),
+        ctx.accounts.sol_vault.as_deref(),
+        ctx.accounts.token_mint.as_deref(),
+        ctx.accounts.token_program.as_ref(),
+    )?;
+    let amount = vault.balance()?;
+    let rolled_over = ctx.accounts.rollover_pool.is_some();
+
+    let (token_destination, wallet_destination, destination_key) = if let Some(rollover_pool) = ctx.accounts.rollover_pool.as_ref() {
+        require_keys_eq!(
+            ctx.accounts.caller.key(),
+            ctx.accounts.protocol.admin,
+            CustomError::Unauthorized
+        );
+        require!(
+            rollover_pool.key() != pool.key()
+                && rollover_pool.asset_kind == pool.asset_kind
+                && rollover_pool.stake_token_mint == pool.stake_token_mint
+                && (rollover_pool.status == PoolStatus::Upcoming
+                    || rollover_pool.status == PoolStatus::Active),
+            CustomError::InvalidRolloverPool
+        );
+
+        let Some(rollover_vault) = ctx.accounts.rollover_vault.as_ref() else {
+            return err!(CustomError::InvalidRolloverPool);
+        };
+        let vault_seed = match rollover_pool.asset_kind {
+            AssetKind::Token => SEED_POOL_VAULT,
+            AssetKind::Native => SEED_SOL_VAULT,
+        };
+        let (expected_vault, _) = Pubkey::find_program_address(
+            &[vault_seed, rollover_pool.key().as_ref()],
+            ctx.program_id,
+        );
+        require_keys_eq!(rollover_vault.key(), expected_vault, CustomError::InvalidRolloverPool);
+
+        let destination = rollover_vault.to_account_info();
+        (Some(destination.clone()), Some(destination), rollover_pool.key())
+    } else {
+        let token_destination = ctx.accounts.treasury_token_account.as_ref().map(|account| account.to_account_info());
+        let wallet_destination = ctx.accounts.treasury_wallet.as_ref().map(|account| account.to_account_info());
+        let destination_key = match pool.asset_kind {
+            AssetKind::Token => token_destination.as_ref().map(|account| account.key()),
+            AssetKind::Native => wallet_destination.as_ref().map(|account| account.key()),
+        }
+        .ok_or(CustomError::AssetAccountsMismatch)?;
+        (token_destination, wallet_destination, destination_key)
+    };
+
+    let rollover_vault = ctx.accounts.rollover_vault.as_ref().filter(|_| rolled_over);
+    let rollover_before = rollover_vault
+        .map(|account| rollover_vault_balance(pool.asset_kind, account))
+        .transpose()?;
+
+    vault.pay(pool, token_destination, wallet_destination, amount)?;
+
+    // Transfer-fee mints deliver less than `amount`, so the rollover pool only counts
+    // what its vault actually received.
+    let rolled_over_amount = match (rollover_vault, rollover_before) {
+        (Some(account), Some(before)) => rollover_vault_balance(pool.asset_kind, account)?
+            .checked_sub(before)
+            .ok_or(CustomError::MathOverflow)?,
+        _ => 0,
+    };
+    vault.close(pool, ctx.accounts.pool_creator.to_account_info())?;
+
+    emit!(UnclaimedSwept {
+        pool: pool.key(),
+        amount,
+        destination: destination_key,
+        rolled_over,
+        unclaimed_bets: pool.total_participants,
+        swept_at: clock.unix_timestamp,
+    });
+
+    // Rolled-over funds are a prize for the next pool, not anyone's stake.
+    if let Some(rollover_pool) = ctx.accounts.rollover_pool.as_mut() {
+        rollover_pool.subsidy_amount = rollover_pool
+            .subsidy_amount
+            .checked_add(rolled_over_amount)
+            .ok_or(CustomError::MathOverflow)?;
+    }
+
+    let pool_info = pool.to_account_info();
+    let creator_info = ctx.accounts.pool_creator.to_account_info();
+
+    let creator_lamports = creator_info.lamports();
+    let pool_lamports = pool_info.lamports();
+
+    **creator_info.lamports.borrow_mut() = creator_lamports.checked_add(pool_lamports).unwrap();
+    **pool_info.lamports.borrow_mut() = 0;
+
+    msg!("Pool {} swept: {} unclaimed tokens moved, accounts closed.", pool.pool_id, amount);
+
+    Ok(())
+}
+
+/// Token amount or lamports held by a rollover vault, for measuring what a transfer added.
+fn rollover_vault_balance(asset_kind: AssetKind, account: &UncheckedAccount) -> Result<u64> {
+    match asset_kind {
+        AssetKind::Token => {
+            let data = account.try_borrow_data()?;
+            Ok(TokenAccount::try_deserialize(&mut &data[..])?.amount)
+        }
+        AssetKind::Native => Ok(account.lamports()),
+    }
+}
@@ -1,11 +1,11 @@
 use anchor_lang::prelude::*;
-use crate::state::{Bet, Pool, PoolStatus, BetStatus};
-use crate::constants::{SEED_BET, SEED_POOL};
+use crate::state::{Bet, Pool, PoolStatus, BetStatus, StakeReceipt};
+use crate::constants::{SEED_BET, SEED_POOL, SEED_STAKE_RECEIPT};
 use crate::errors::CustomError;
 
-/// TEE-only instruction: updates prediction and optionally records a stake increase.
-/// Token transfers for stake increases MUST be handled separately via `add_stake` on L1
-/// before calling this instruction, since the pool vault lives on L1 (not delegated to TEE).
+/// TEE-only instruction: updates prediction and optionally credits a stake increase.
+/// Stake can only grow by consuming the bet's `StakeReceipt`, written by `add_stake` on L1
+/// when the tokens reach the vault; the rollup reads it as a cloned, read-only account.
 #[derive(Accounts)]
 pub struct UpdateBet<'info> {
     #[account(mut)]
@@ -27,12 +27,20 @@ pub struct UpdateBet<'info> {
         constraint = bet.status == BetStatus::Active @ CustomError::AlreadyClaimed
     )]
     pub bet: Box<Account<'info, Bet>>,
+
+    /// Pass to credit top-ups deposited with `add_stake` since the last update.
+    #[account(
+        seeds = [SEED_STAKE_RECEIPT, bet.key().as_ref()],
+        bump = stake_receipt.bump,
+        constraint = stake_receipt.bet == bet.key() @ CustomError::StakeReceiptMismatch
+    )]
+    pub stake_receipt: Option<Box<Account<'info, StakeReceipt>>>,
 }
 
 pub fn update_bet(
     ctx: Context<UpdateBet>,
     new_prediction: u64,
-    additional_stake: u64,
+    new_prediction_high: Option<u64>,
 ) -> Result<()> {
     let pool = &ctx.accounts.pool;
     let clock = Clock::get()?;
@@ -41,14 +49,31 @@ pub fn update_bet(
         CustomError::MarketClosed
     );
     require!(clock.unix_timestamp < pool.cutoff_time, CustomError::MarketClosed);
+    pool.validate_prediction(new_prediction, new_prediction_high)?;
 
     let bet = &mut ctx.accounts.bet;
 
     bet.update_count = bet.update_count.checked_add(1).unwrap();
     bet.prediction = new_prediction;
+    bet.prediction_high = new_prediction_high.unwrap_or(new_prediction);
+
+    if pool.reset_bonus_on_update {
+        bet.bonus_entry_ts = clock.unix_timestamp;
+    }
+
+    let additional_stake = ctx
+        .accounts
+        .stake_receipt
+        .as_ref()
+        .map_or(0, |receipt| receipt.pending(bet.credited_top_ups));
 
     if additional_stake > 0 {
-        bet.stake = bet.stake.checked_add(additional_stake).unwrap();
+        let receipt = ctx.accounts.stake_receipt.as_ref().unwrap();
+        let new_stake = bet.stake.checked_add(additional_stake).unwrap();
+        pool.check_bet_stake(additional_stake, new_stake)?;
+        bet.stake = new_stake;
+        bet.credited_top_ups = receipt.total_amount;
+        bet.receipt_nonce = receipt.nonce;
         msg!("Bet Updated: Prediction={}, Stake+={}", new_prediction, additional_stake);
     } else {
         msg!("Bet Updated: Prediction={}", new_prediction);
@@ -0,0 +1,170 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::{Pool, PoolStatus, Protocol, ResolutionProposal, SolVault, VoidReason};
+use crate::constants::{
+    SEED_BOND_VAULT, SEED_PROTOCOL, SEED_POOL, SEED_POOL_VAULT, SEED_PROPOSAL, SEED_SOL_VAULT,
+};
+use crate::errors::CustomError;
+use crate::utils::{DisputeBonds, PoolVault};
+use crate::events::{PoolVoided, SubsidyReturned};
+
+#[derive(Accounts)]
+pub struct VoidPool<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_PROTOCOL],
+        bump,
+        constraint = protocol.admin == admin.key() @ CustomError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+
+    #[account(
+        mut,
+        seeds = [SEED_POOL, pool.created_by.as_ref(), &(pool.pool_id.to_le_bytes())],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// Token pools only.
+    #[account(
+        mut,
+        seeds = [SEED_POOL_VAULT, pool.key().as_ref()],
+        bump,
+        token::authority = pool,
+    )]
+    pub pool_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Native SOL pools only.
+    #[account(
+        mut,
+        seeds = [SEED_SOL_VAULT, pool.key().as_ref()],
+        bump = sol_vault.bump
+    )]
+    pub sol_vault: Option<Account<'info, SolVault>>,
+
+    /// Token pools only. Receives any creator subsidy still sitting in the vault.
+    #[account(
+        mut,
+        token::mint = pool.stake_token_mint,
+        token::authority = pool.created_by,
+    )]
+    pub creator_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Receives the subsidy of native SOL pools.
+    #[account(mut, address = pool.created_by @ CustomError::Unauthorized)]
+    pub pool_creator: Option<UncheckedAccount<'info>>,
+
+    /// Token pools only; writable so transfer fees withheld on a bond vault can be
+    /// harvested before it closes.
+    #[account(mut, address = pool.stake_token_mint @ CustomError::PoolMismatch)]
+    pub token_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    /// Disputed pools only: the proposal the arbiter never settled. Closed to its
+    /// proposer once the dispute times out.
+    #[account(
+        mut,
+        seeds = [SEED_PROPOSAL, pool.key().as_ref()],
+        bump = proposal.bump,
+    )]
+    pub proposal: Option<Box<Account<'info, ResolutionProposal>>>,
+
+    /// Disputed pools only.
+    #[account(
+        mut,
+        seeds = [SEED_BOND_VAULT, pool.key().as_ref()],
+        bump,
+        token::authority = pool,
+    )]
+    pub bond_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// CHECK: Disputed pools only. Receives the proposal and bond vault rent;
+    /// validated against the proposal in the handler.
+    #[account(mut)]
+    pub proposer: Option<UncheckedAccount<'info>>,
+
+    /// Disputed pools only; owner validated against the proposal in the handler.
+    #[account(mut, token::mint = pool.stake_token_mint)]
+    pub proposer_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Disputed pools only; owner validated against the proposal in the handler.
+    #[account(mut, token::mint = pool.stake_token_mint)]
+    pub disputer_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+}
+
+/// Voids a pool that cannot be resolved fairly. Skips weight calculation and
+/// finalization entirely: every bet can then claim back its exact stake, fee-free.
+/// Pools with an open proposal must be arbitrated or settled first, unless the arbiter
+/// has left a dispute unsettled for `DISPUTE_TIMEOUT`; both bonds are then refunded.
+/// Any creator subsidy is returned to the creator up front.
+pub fn void_pool(ctx: Context<VoidPool>, reason: VoidReason) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let clock = Clock::get()?;
+
+    require!(reason != VoidReason::None, CustomError::PoolNotVoidable);
+    require!(
+        pool.status == PoolStatus::Upcoming
+            || pool.status == PoolStatus::Active
+            || pool.status == PoolStatus::Closed
+            || pool.status == PoolStatus::Resolving
+            || pool.status == PoolStatus::Disputed,
+        CustomError::PoolNotVoidable
+    );
+
+    if pool.status == PoolStatus::Disputed {
+        let bonds = DisputeBonds::resolve(
+            ctx.accounts.proposal.as_deref(),
+            ctx.accounts.bond_vault.as_deref(),
+            ctx.accounts.proposer.as_ref().map(|account| account.to_account_info()),
+            ctx.accounts.proposer_token_account.as_deref(),
+            ctx.accounts.disputer_token_account.as_deref(),
+            clock.unix_timestamp,
+        )?;
+        bonds.refund(
+            pool,
+            ctx.accounts.token_mint.as_deref().ok_or(CustomError::DisputeAccountsMissing)?,
+            ctx.accounts.token_program.as_ref().ok_or(CustomError::DisputeAccountsMissing)?,
+        )?;
+    }
+
+    pool.void(reason, clock.unix_timestamp);
+
+    let subsidy = pool.subsidy_amount;
+    if subsidy > 0 {
+        let vault = PoolVault::resolve(
+            pool,
+            ctx.accounts.pool_vault.as_ref(),
+            ctx.accounts.sol_vault.as_ref(),
+            ctx.accounts.token_mint.as_deref(),
+            ctx.accounts.token_program.as_ref(),
+        )?;
+        vault.pay(
+            pool,
+            ctx.accounts.creator_token_account.as_ref().map(|account| account.to_account_info()),
+            ctx.accounts.pool_creator.as_ref().map(|account| account.to_account_info()),
+            subsidy,
+        )?;
+
+        pool.subsidy_amount = 0;
+
+        emit!(SubsidyReturned {
+            pool: pool.key(),
+            creator: pool.created_by,
+            amount: subsidy,
+        });
+    }
+
+    emit!(PoolVoided {
+        pool: pool.key(),
+        reason,
+        total_staked: pool.total_staked,
+        voided_at: clock.unix_timestamp,
+    });
+
+    msg!("Pool {} voided by admin.", pool.pool_id);
+
+    Ok(())
+}
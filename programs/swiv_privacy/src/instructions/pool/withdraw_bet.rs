@@ -0,0 +1,178 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::{Bet, BetStatus, Pool, PoolStatus, Protocol, SolVault, StakeReceipt};
+use crate::constants::{
+    BPS_DENOMINATOR, SEED_BET, SEED_POOL, SEED_POOL_VAULT, SEED_PROTOCOL, SEED_SOL_VAULT,
+    SEED_STAKE_RECEIPT,
+};
+use crate::errors::CustomError;
+use crate::events::BetWithdrawn;
+use crate::utils::PoolVault;
+
+/// L1 instruction: the bet must be committed back from the rollup first, otherwise it
+/// is still owned by the delegation program and fails to deserialize here.
+#[derive(Accounts)]
+pub struct WithdrawBet<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(seeds = [SEED_PROTOCOL], bump)]
+    pub protocol: Box<Account<'info, Protocol>>,
+
+    /// CHECK: Gas sponsor that paid the bet rent at init_bet; receives it back on a full exit.
+    #[account(mut, address = bet.sponsor @ CustomError::Unauthorized)]
+    pub sponsor: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_POOL, pool.created_by.as_ref(), &(pool.pool_id.to_le_bytes())],
+        bump = pool.bump
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// Token pools only.
+    #[account(
+        mut,
+        seeds = [SEED_POOL_VAULT, pool.key().as_ref()],
+        bump,
+        token::authority = pool,
+    )]
+    pub pool_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Native SOL pools only.
+    #[account(
+        mut,
+        seeds = [SEED_SOL_VAULT, pool.key().as_ref()],
+        bump = sol_vault.bump
+    )]
+    pub sol_vault: Option<Box<Account<'info, SolVault>>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_BET, pool.key().as_ref(), user.key().as_ref()],
+        bump = bet.bump,
+        constraint = bet.user_pubkey == user.key() @ CustomError::Unauthorized,
+        constraint = bet.pool_pubkey == pool.key() @ CustomError::PoolMismatch,
+        constraint = bet.status == BetStatus::Active @ CustomError::AlreadyClaimed
+    )]
+    pub bet: Box<Account<'info, Bet>>,
+
+    /// CHECK: The bet's top-up receipt PDA, required even if `add_stake` never created
+    /// it so a full exit cannot leave deposits behind; loaded in the handler when present.
+    #[account(
+        mut,
+        seeds = [SEED_STAKE_RECEIPT, bet.key().as_ref()],
+        bump
+    )]
+    pub stake_receipt: UncheckedAccount<'info>,
+
+    /// Token pools only.
+    #[account(
+        mut,
+        token::mint = pool.stake_token_mint
+    )]
+    pub user_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Token pools only.
+    #[account(address = pool.stake_token_mint @ CustomError::PoolMismatch)]
+    pub token_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+/// Withdraws `amount` of a bet's stake before `cutoff_time`. The pool's exit penalty is
+/// kept in the vault and paid out to the remaining bettors; top-ups never credited by
+/// `update_bet` are refunded in full. Withdrawing the whole stake closes the bet (and
+/// its receipt) and frees the participant slot.
+pub fn withdraw_bet<'info>(ctx: Context<'info, WithdrawBet<'info>>, amount: u64) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let bet = &mut ctx.accounts.bet;
+    let clock = Clock::get()?;
+
+    require!(
+        pool.status == PoolStatus::Active || pool.status == PoolStatus::Upcoming,
+        CustomError::MarketClosed
+    );
+    require!(clock.unix_timestamp < pool.cutoff_time, CustomError::MarketClosed);
+
+    let receipt = if ctx.accounts.stake_receipt.data_is_empty() {
+        None
+    } else {
+        Some(Account::<StakeReceipt>::try_from(&ctx.accounts.stake_receipt)?)
+    };
+
+    // On L1 the receipt is authoritative: uncredited top-ups go straight back to the user
+    // and the receipt is marked consumed so the rollup can never credit them later.
+    let pending = receipt.as_ref().map_or(0, |receipt| receipt.pending(bet.credited_top_ups));
+    if let Some(receipt) = receipt.as_ref() {
+        bet.credited_top_ups = receipt.total_amount;
+        bet.receipt_nonce = receipt.nonce;
+    }
+
+    require!(amount > 0 && amount <= bet.stake, CustomError::InvalidWithdrawAmount);
+
+    let remaining = bet.stake.checked_sub(amount).unwrap();
+    let is_full_exit = remaining == 0;
+    if !is_full_exit {
+        require!(remaining >= pool.min_stake, CustomError::StakeBelowMinimum);
+    }
+
+    let penalty = (amount as u128)
+        .checked_mul(pool.exit_penalty_bps as u128)
+        .unwrap()
+        .checked_div(BPS_DENOMINATOR as u128)
+        .unwrap() as u64;
+    let payout = amount.checked_sub(penalty).unwrap().checked_add(pending).unwrap();
+
+    let vault = PoolVault::resolve(
+        pool,
+        ctx.accounts.pool_vault.as_deref(),
+        ctx.accounts.sol_vault.as_deref(),
+        ctx.accounts.token_mint.as_deref(),
+        ctx.accounts.token_program.as_ref(),
+    )?;
+    vault.pay(
+        pool,
+        ctx.accounts.user_token_account.as_ref().map(|account| account.to_account_info()),
+        Some(ctx.accounts.user.to_account_info()),
+        payout,
+    )?;
+
+    // The penalty never leaves the vault, so total_staked only drops by what was paid out.
+    pool.total_staked = pool.total_staked.checked_sub(payout).unwrap();
+
+    let referred_after = bet.referred_amount.min(remaining);
+    let referred_removed = bet.referred_amount.checked_sub(referred_after).unwrap();
+    pool.referred_stake = pool.referred_stake.saturating_sub(referred_removed);
+    bet.referred_amount = referred_after;
+    bet.stake = remaining;
+
+    if is_full_exit {
+        pool.total_participants = pool.total_participants.saturating_sub(1);
+        // A closed bet is never scored, so none of its deposits remain pending.
+        if let Some(receipt) = receipt.as_ref() {
+            pool.pending_top_ups = pool.pending_top_ups.saturating_sub(receipt.total_amount);
+        }
+    }
+
+    emit!(BetWithdrawn {
+        bet: bet.key(),
+        user: ctx.accounts.user.key(),
+        amount,
+        penalty,
+        remaining_stake: remaining,
+        closed: is_full_exit,
+    });
+
+    if is_full_exit {
+        if let Some(receipt) = receipt.as_ref() {
+            receipt.close(ctx.accounts.user.to_account_info())?;
+        }
+        bet.close(ctx.accounts.sponsor.to_account_info())?;
+        msg!("Bet withdrawn in full and closed. Penalty kept in pool: {}", penalty);
+    } else {
+        msg!("Bet reduced by {}. Penalty kept in pool: {}", amount, penalty);
+    }
+
+    Ok(())
+}
@@ -9,6 +9,9 @@ pub mod state;
 pub mod utils;
 
 use instructions::*;
+use state::{
+    AssetKind, BondModeration, ConvictionConfig, OracleConfig, OutcomeConfig, ScoringRule, StakeLimits, TimeBonusConfig, VoidReason,
+};
 
 declare_id!("EG3bvFiDtENf5m5tT3JLq1cATQea97Ckf7KE1WXiRqy6");
 
@@ -22,32 +25,92 @@ pub mod swiv_privacy {
     pub fn initialize_protocol(
         ctx: Context<InitializeProtocol>,
         protocol_fee_bps: u64,
+        config_delay: i64,
     ) -> Result<()> {
-        admin::initialize_protocol(ctx, protocol_fee_bps)
+        admin::initialize_protocol(ctx, protocol_fee_bps, config_delay)
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn update_config(
         ctx: Context<UpdateConfig>,
         new_treasury: Option<Pubkey>,
         new_protocol_fee_bps: Option<u64>,
         new_batch_settle_wait_duration: Option<i64>,
+        new_dispute_window: Option<i64>,
+        new_resolution_bond: Option<u64>,
+        new_arbiter: Option<Pubkey>,
+        new_creation_bond: Option<u64>,
+        new_referral_fee_bps: Option<u64>,
+        new_config_delay: Option<i64>,
+        new_min_claim_window: Option<i64>,
     ) -> Result<()> {
         admin::update_config(
             ctx,
             new_treasury,
             new_protocol_fee_bps,
             new_batch_settle_wait_duration,
+            new_dispute_window,
+            new_resolution_bond,
+            new_arbiter,
+            new_creation_bond,
+            new_referral_fee_bps,
+            new_config_delay,
+            new_min_claim_window,
         )
     }
 
-    pub fn transfer_admin(ctx: Context<TransferAdmin>, new_admin: Pubkey) -> Result<()> {
-        admin::transfer_admin(ctx, new_admin)
+    /// Applies the change queued by `update_config` once its timelock has passed.
+    pub fn execute_config_change(ctx: Context<ExecuteConfigChange>) -> Result<()> {
+        admin::execute_config_change(ctx)
+    }
+
+    pub fn cancel_config_change(ctx: Context<CancelConfigChange>) -> Result<()> {
+        admin::cancel_config_change(ctx)
+    }
+
+    /// Starts a two-step admin handover; `new_admin` must then call `accept_admin`.
+    pub fn propose_admin(ctx: Context<ProposeAdmin>, new_admin: Pubkey) -> Result<()> {
+        admin::propose_admin(ctx, new_admin)
+    }
+
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        admin::accept_admin(ctx)
+    }
+
+    pub fn cancel_admin_transfer(ctx: Context<CancelAdminTransfer>) -> Result<()> {
+        admin::cancel_admin_transfer(ctx)
+    }
+
+    /// Reallocs a `protocol_v1` account written under an older layout and upgrades it
+    /// to `Protocol::VERSION`.
+    pub fn migrate_protocol(ctx: Context<MigrateProtocol>) -> Result<()> {
+        admin::migrate_protocol(ctx)
+    }
+
+    pub fn create_committee(
+        ctx: Context<CreateCommittee>,
+        committee_id: u64,
+        reporters: Vec<Pubkey>,
+        threshold: u8,
+        outlier_threshold_bps: u64,
+    ) -> Result<()> {
+        admin::create_committee(ctx, committee_id, reporters, threshold, outlier_threshold_bps)
     }
 
     pub fn set_pause(ctx: Context<SetPause>, paused: bool) -> Result<()> {
         admin::set_pause(ctx, paused)
     }
 
+    /// Grants `roles` (a bit set of `ROLE_*`) to `holder`, creating its roles PDA if needed.
+    pub fn grant_role(ctx: Context<GrantRole>, holder: Pubkey, roles: u8) -> Result<()> {
+        admin::grant_role(ctx, holder, roles)
+    }
+
+    /// Revokes `roles` from the holder of the roles PDA, closing it once empty.
+    pub fn revoke_role(ctx: Context<RevokeRole>, roles: u8) -> Result<()> {
+        admin::revoke_role(ctx, roles)
+    }
+
     // --- DELEGATION ---
     pub fn delegate_pool<'info>(ctx: Context<'info, DelegatePool<'info>>, pool_id: u64) -> Result<()> {
         instructions::delegation::delegate_pool(ctx, pool_id)
@@ -84,13 +147,24 @@ pub mod swiv_privacy {
     }
 
     // --- POOL ---
+    #[allow(clippy::too_many_arguments)]
     pub fn create_pool(
         ctx: Context<CreatePool>,
         title: String,
         start_time: i64,
         end_time: i64,
         max_accuracy_buffer: u64,
-        conviction_bonus_bps: u64,
+        conviction: ConvictionConfig,
+        oracle: Option<OracleConfig>,
+        scoring_rule: ScoringRule,
+        time_bonus: TimeBonusConfig,
+        outcome: OutcomeConfig,
+        creator_fee_bps: u64,
+        subsidy_amount: u64,
+        limits: StakeLimits,
+        exit_penalty_bps: u64,
+        claim_window: i64,
+        asset_kind: AssetKind,
     ) -> Result<()> {
         pool::create_pool(
             ctx,
@@ -98,22 +172,49 @@ pub mod swiv_privacy {
             start_time,
             end_time,
             max_accuracy_buffer,
-            conviction_bonus_bps,
+            conviction,
+            oracle,
+            scoring_rule,
+            time_bonus,
+            outcome,
+            creator_fee_bps,
+            subsidy_amount,
+            limits,
+            exit_penalty_bps,
+            claim_window,
+            asset_kind,
         )
     }
 
     // --- BET ---
-    pub fn place_bet(ctx: Context<PlaceBet>, prediction: u64, request_id: String) -> Result<()> {
-        pool::place_bet(ctx, prediction, request_id)
+    /// `prediction_high` turns the bet into a `[prediction, prediction_high]` interval.
+    pub fn place_bet(
+        ctx: Context<PlaceBet>,
+        prediction: u64,
+        prediction_high: Option<u64>,
+        request_id: String,
+    ) -> Result<()> {
+        pool::place_bet(ctx, prediction, prediction_high, request_id)
     }
 
-    pub fn init_bet(ctx: Context<InitBet>, amount: u64, request_id: String) -> Result<()> {
-        pool::init_bet(ctx, amount, request_id)
+    /// `referrer` is recorded on the bet and earns a share of the protocol fee at claim time.
+    pub fn init_bet(
+        ctx: Context<InitBet>,
+        amount: u64,
+        request_id: String,
+        referrer: Option<Pubkey>,
+    ) -> Result<()> {
+        pool::init_bet(ctx, amount, request_id, referrer)
     }
     pub fn resolve_pool(ctx: Context<ResolvePool>, final_outcome: u64) -> Result<()> {
         pool::resolve_pool(ctx, final_outcome)
     }
 
+    /// Permissionless: resolves a pool from its configured Pyth price account.
+    pub fn resolve_pool_from_oracle(ctx: Context<ResolvePoolFromOracle>) -> Result<()> {
+        pool::resolve_pool_from_oracle(ctx)
+    }
+
     pub fn batch_calculate_weights<'info>(
         ctx: Context<'info, BatchCalculateWeights<'info>>,
     ) -> Result<()> {
@@ -128,20 +229,57 @@ pub mod swiv_privacy {
         pool::claim_reward(ctx)
     }
 
+    /// Permissionless: opens a referrer's fee ledger and vault for one mint.
+    pub fn init_referral_stats(ctx: Context<InitReferralStats>) -> Result<()> {
+        pool::init_referral_stats(ctx)
+    }
+
+    pub fn claim_referral_fees(ctx: Context<ClaimReferralFees>) -> Result<()> {
+        pool::claim_referral_fees(ctx)
+    }
+
     pub fn update_bet(
         ctx: Context<UpdateBet>,
         new_prediction: u64,
-        additional_stake: u64,
+        new_prediction_high: Option<u64>,
     ) -> Result<()> {
-        pool::update_bet(ctx, new_prediction, additional_stake)
+        pool::update_bet(ctx, new_prediction, new_prediction_high)
     }
 
-    /// L1 instruction: transfers tokens from user to pool vault and updates pool volume.
-    /// Call this BEFORE `update_bet` on TEE when increasing stake.
+    /// L1 instruction: transfers tokens from user to pool vault, updates pool volume and
+    /// records the deposit on the bet's `StakeReceipt`. Call this BEFORE `update_bet` on
+    /// TEE, which credits the receipt to the bet.
     pub fn add_stake(ctx: Context<AddStake>, amount: u64) -> Result<()> {
         pool::add_stake(ctx, amount)
     }
 
+    /// L1 instruction: full or partial exit before cutoff, minus the pool's exit penalty.
+    /// The bet must have been committed back from the TEE.
+    pub fn withdraw_bet<'info>(ctx: Context<'info, WithdrawBet<'info>>, amount: u64) -> Result<()> {
+        pool::withdraw_bet(ctx, amount)
+    }
+
+    /// After the claim deadline, moves what is left in the vault to the treasury (or,
+    /// admin only, into another pool's subsidy) and closes the pool and vault.
+    pub fn sweep_unclaimed(ctx: Context<SweepUnclaimed>) -> Result<()> {
+        pool::sweep_unclaimed(ctx)
+    }
+
+    /// Returns the rent of a bet, and its stake receipt, whose pool has been swept.
+    pub fn close_orphaned_bet(ctx: Context<CloseOrphanedBet>) -> Result<()> {
+        pool::close_orphaned_bet(ctx)
+    }
+
+    /// Reallocs a pool written under an older layout and upgrades it to `Pool::VERSION`.
+    pub fn migrate_pool(ctx: Context<MigratePool>) -> Result<()> {
+        pool::migrate_pool(ctx)
+    }
+
+    /// Reallocs a bet written under an older layout and upgrades it to `Bet::VERSION`.
+    pub fn migrate_bet(ctx: Context<MigrateBet>) -> Result<()> {
+        pool::migrate_bet(ctx)
+    }
+
     pub fn emergency_refund(ctx: Context<EmergencyRefund>) -> Result<()> {
         pool::emergency_refund(ctx)
     }
@@ -149,4 +287,45 @@ pub mod swiv_privacy {
     pub fn cancel_pool(ctx: Context<CancelPool>) -> Result<()> {
         pool::cancel_pool(ctx)
     }
+
+    /// Permissionless: returns the creator bond of a resolved pool.
+    pub fn release_creator_bond(ctx: Context<ReleaseCreatorBond>) -> Result<()> {
+        pool::release_creator_bond(ctx)
+    }
+
+    /// Admin moderation: release or slash a pool creator's bond.
+    pub fn moderate_creator_bond(
+        ctx: Context<ModerateCreatorBond>,
+        action: BondModeration,
+    ) -> Result<()> {
+        admin::moderate_creator_bond(ctx, action)
+    }
+
+    /// Voids a pool so every bet can claim back its exact stake, fee-free.
+    pub fn void_pool(ctx: Context<VoidPool>, reason: VoidReason) -> Result<()> {
+        pool::void_pool(ctx, reason)
+    }
+
+    // --- OPTIMISTIC RESOLUTION ---
+    pub fn propose_outcome(ctx: Context<ProposeOutcome>, proposed_outcome: u64) -> Result<()> {
+        pool::propose_outcome(ctx, proposed_outcome)
+    }
+
+    pub fn dispute_outcome(ctx: Context<DisputeOutcome>) -> Result<()> {
+        pool::dispute_outcome(ctx)
+    }
+
+    /// Permissionless: finalizes an undisputed proposal after the dispute window.
+    pub fn settle_proposal(ctx: Context<SettleProposal>) -> Result<()> {
+        pool::settle_proposal(ctx)
+    }
+
+    pub fn arbitrate_dispute(ctx: Context<ArbitrateDispute>, final_outcome: u64) -> Result<()> {
+        pool::arbitrate_dispute(ctx, final_outcome)
+    }
+
+    // --- COMMITTEE RESOLUTION ---
+    pub fn submit_report(ctx: Context<SubmitReport>, outcome: u64) -> Result<()> {
+        pool::submit_report(ctx, outcome)
+    }
 }
\ No newline at end of file
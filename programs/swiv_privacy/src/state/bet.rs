@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
+use crate::errors::CustomError;
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
 pub enum BetStatus {
     /// Reserved, currently unused. init_bet sets new bets directly to `Active`.
     /// Kept as the first variant to preserve borsh discriminants for existing accounts.
@@ -11,6 +12,7 @@ pub enum BetStatus {
 }
 
 #[account]
+#[derive(InitSpace)]
 pub struct Bet {
     pub user_pubkey: Pubkey,
     pub pool_pubkey: Pubkey,
@@ -29,8 +31,49 @@ pub struct Bet {
     pub status: BetStatus,
     
     pub bump: u8,
+
+    /// Prediction error recorded by batch_calculate_weights, used to settle `WinnerTakeClosest` pools.
+    pub scored_error: u64,
+    /// Entry time used for the time bonus. Equals creation_ts unless the pool
+    /// resets the bonus clock on prediction changes.
+    pub bonus_entry_ts: i64,
+    /// Upper bound of an interval prediction, with `prediction` as the lower bound.
+    /// Equal to `prediction` for point bets.
+    pub prediction_high: u64,
+
+    /// Wallet credited with a share of the protocol fee on this bet; default if none.
+    pub referrer: Pubkey,
+    /// Stake attributed to `referrer`, recorded at init_bet.
+    pub referred_amount: u64,
+
+    /// Portion of the bet's `StakeReceipt.total_amount` already added to `stake`.
+    pub credited_top_ups: u64,
+    /// `StakeReceipt.nonce` at the last credit.
+    pub receipt_nonce: u64,
+
+    /// Wallet that paid the bet rent at init_bet; refunded when the bet closes.
+    pub sponsor: Pubkey,
+
+    /// Layout version; 0 for bets created before versioning. See `migrate_bet`.
+    pub version: u8,
 }
 
 impl Bet {
-    pub const SPACE: usize = 250; 
+    pub const SPACE: usize = 8 + Bet::INIT_SPACE;
+    pub const VERSION: u8 = 1;
+
+    /// Brings a bet read under an older layout up to `VERSION`. Bets that predate
+    /// `sponsor` had their rent paid by `protocol.admin`, the only sponsor `init_bet`
+    /// accepted then, so the refund goes to the admin; after an admin handover that is
+    /// the new admin, which inherits the old one's sponsorship along with the protocol.
+    pub fn upgrade(&mut self, admin: Pubkey) -> Result<()> {
+        require!(self.version < Self::VERSION, CustomError::AccountUpToDate);
+
+        if self.sponsor == Pubkey::default() {
+            self.sponsor = admin;
+        }
+
+        self.version = Self::VERSION;
+        Ok(())
+    }
 }
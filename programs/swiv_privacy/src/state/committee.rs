@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::constants::MAX_COMMITTEE_SIZE;
+
+/// A set of registered reporter keys. Pools that opt in at creation are resolved
+/// to the median of the first `threshold` reports instead of by the admin.
+#[account]
+pub struct ResolverCommittee {
+    pub committee_id: u64,
+    pub reporters: Vec<Pubkey>,
+    pub threshold: u8,
+    /// Reports further than this from the median (bps of the median) are flagged for audit.
+    pub outlier_threshold_bps: u64,
+    pub bump: u8,
+}
+
+impl ResolverCommittee {
+    pub const SPACE: usize = 8 + 8 + (4 + 32 * MAX_COMMITTEE_SIZE) + 1 + 8 + 1;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CommitteeReport {
+    pub reporter: Pubkey,
+    pub outcome: u64,
+}
+
+/// Reports collected for a single pool.
+#[account]
+pub struct CommitteeReports {
+    pub pool: Pubkey,
+    pub committee: Pubkey,
+    pub reports: Vec<CommitteeReport>,
+    pub bump: u8,
+}
+
+impl CommitteeReports {
+    pub const SPACE: usize = 8 + 32 + 32 + (4 + (32 + 8) * MAX_COMMITTEE_SIZE) + 1;
+}
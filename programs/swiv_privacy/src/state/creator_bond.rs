@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+/// Lamport bond escrowed by a pool's creator at `create_pool`. Returned after a
+/// clean resolution or slashed to the treasury by the admin.
+#[account]
+pub struct CreatorBond {
+    pub pool: Pubkey,
+    pub creator: Pubkey,
+    /// Lamports held above the account's rent. Zero for admin-created pools.
+    pub amount: u64,
+    pub bump: u8,
+}
+
+impl CreatorBond {
+    pub const SPACE: usize = 8 + 32 + 32 + 8 + 1;
+}
+
+/// Admin decision on a creator bond.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum BondModeration {
+    /// Return the bond to the creator.
+    Release,
+    /// Send the bond to the treasury (spam or invalid question).
+    Slash,
+}
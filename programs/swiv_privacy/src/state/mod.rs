@@ -1,7 +1,21 @@
 pub mod protocol;
 pub mod pool;
 pub mod bet;
+pub mod resolution;
+pub mod committee;
+pub mod creator_bond;
+pub mod referral;
+pub mod stake_receipt;
+pub mod sol_vault;
+pub mod roles;
 
 pub use protocol::*;
 pub use pool::*;
-pub use bet::*;
\ No newline at end of file
+pub use bet::*;
+pub use resolution::*;
+pub use committee::*;
+pub use creator_bond::*;
+pub use referral::*;
+pub use stake_receipt::*;
+pub use sol_vault::*;
+pub use roles::*;
\ No newline at end of file
@@ -1,6 +1,11 @@
 use anchor_lang::prelude::*;
+use crate::constants::{
+    DEFAULT_CLAIM_WINDOW, LEGACY_TIME_BONUS_MAX_BPS, MAX_OUTCOMES, MAX_OUTCOME_LABEL_LENGTH,
+    MAX_TITLE_LENGTH,
+};
+use crate::errors::CustomError;
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
 pub enum PoolStatus {
     Upcoming,
     Active,
@@ -9,11 +14,132 @@ pub enum PoolStatus {
     Resolved,
     Settled,
     Cancelled,
+    /// An optimistic outcome has been proposed and its dispute window is open.
+    /// Lifecycle-wise this sits between `Closed` and `Resolving`; it is appended
+    /// here to preserve borsh discriminants for existing accounts.
+    Proposed,
+    /// The proposal was challenged and awaits the arbiter.
+    Disputed,
+    /// Voided by the admin; every bet is refunded its exact stake without fees.
+    Voided,
+}
+
+/// Why a pool was voided. `None` for pools that were never voided.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum VoidReason {
+    None,
+    /// The outcome source was unavailable or unreliable.
+    DataUnavailable,
+    /// The underlying event was cancelled or postponed.
+    EventCancelled,
+    /// The question turned out to be ambiguous or unresolvable.
+    AmbiguousOutcome,
+    /// The pool was set up incorrectly.
+    MisconfiguredPool,
+    Other,
+    /// The arbiter did not settle a dispute within `DISPUTE_TIMEOUT`.
+    DisputeTimedOut,
+}
+
+/// What a bet's `prediction` means for this pool.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum PoolKind {
+    /// A `u64` value scored by distance from the result.
+    Numeric,
+    /// An index into `outcome_labels`; only bets on the winning index earn weight.
+    Categorical,
+    /// An `i64` value carried in the `u64` prediction/result fields as two's
+    /// complement. Zero and negative values are ordinary outcomes.
+    SignedNumeric,
+}
+
+/// Outcome settings supplied at `create_pool`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct OutcomeConfig {
+    pub kind: PoolKind,
+    /// Outcome names; required for categorical pools and empty otherwise.
+    pub labels: Vec<String>,
+    /// Decimal exponent of numeric outcomes (e.g. -2 means 1 unit = 0.01).
+    pub exponent: i32,
+    /// When non-zero, the hyperbolic rule measures error relative to this value
+    /// instead of the result. Required for hyperbolic signed pools.
+    pub accuracy_scale: u64,
+}
+
+/// Curve used to turn a bet's prediction error into an accuracy score.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum ScoringRule {
+    /// `1 / (1 + 10 * relative_error)`, the original Swiv curve.
+    Hyperbolic,
+    /// Falls linearly from 1 at zero error to 0 at `max_accuracy_buffer`.
+    LinearDecay,
+    /// Halves every `half_life` units of absolute error.
+    ExponentialDecay { half_life: u64 },
+    /// Fixed score buckets at 1/4, 1/2 and all of `max_accuracy_buffer`.
+    StepBuckets,
+    /// Only the bets with the smallest error share the pot.
+    WinnerTakeClosest,
+}
+
+/// Shape of the early-entry bonus over the betting window.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum TimeBonusCurve {
+    None,
+    Linear,
+    Quadratic,
+    Step,
+}
+
+/// Early-entry bonus settings supplied at `create_pool`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TimeBonusConfig {
+    pub curve: TimeBonusCurve,
+    /// Extra multiplier at the very start of the window, in bps (15000 = +1.5x).
+    pub max_bonus_bps: u64,
+    /// When set, changing a prediction restarts the bet's bonus clock.
+    pub reset_on_update: bool,
+}
+
+/// Oracle settings supplied at `create_pool` for pools resolved from a Pyth price account.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct OracleConfig {
+    pub feed: Pubkey,
+    pub exponent: i32,
+    pub max_staleness: i64,
+}
+
+/// Conviction curve supplied at `create_pool`. The multiplier for a bet revised
+/// `n` times is `max(1 + bonus - n * decay, floor)`, all in basis points.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ConvictionConfig {
+    pub bonus_bps: u64,
+    pub decay_bps: u64,
+    pub floor_bps: u64,
+}
+
+/// What a pool's stakes are held in.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum AssetKind {
+    /// SPL Token or Token-2022 units of `stake_token_mint`, held in the pool vault.
+    Token,
+    /// Plain lamports held in the pool's `SolVault`; `stake_token_mint` is unset.
+    Native,
+}
+
+/// Stake limits supplied at `create_pool`. A zero maximum means "no cap"; bets of
+/// zero are always rejected, whatever `min_stake` says.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct StakeLimits {
+    pub min_stake: u64,
+    pub max_stake_per_bet: u64,
+    pub max_pool_total: u64,
 }
 
 #[account]
+#[derive(InitSpace)]
 pub struct Pool {
     pub created_by: Pubkey,
+    #[max_len(MAX_TITLE_LENGTH)]
     pub title: String,
     pub pool_id: u64,
     pub stake_token_mint: Pubkey,
@@ -28,6 +154,7 @@ pub struct Pool {
     pub distributable_amount: u64,
 
     pub max_accuracy_buffer: u64,
+    /// Extra multiplier, in bps, for bets never revised after their first prediction.
     pub conviction_bonus_bps: u64,
 
     pub resolution_result: u64,
@@ -41,4 +168,207 @@ pub struct Pool {
     pub status: PoolStatus,
 
     pub bump: u8,
+
+    /// Pyth price account used by `resolve_pool_from_oracle`. `Pubkey::default()` means manual resolution only.
+    pub oracle_feed: Pubkey,
+    /// Decimal exponent of the pool's fixed-point outcome (e.g. -6 means 1 unit = 0.000001).
+    pub oracle_exponent: i32,
+    /// Latest accepted oracle publish time, in seconds after `end_time`.
+    pub oracle_max_staleness: i64,
+
+    /// Resolver committee that must resolve this pool. `Pubkey::default()` means none.
+    pub resolver_committee: Pubkey,
+
+    /// Multiplier, in bps, removed from the conviction bonus per revision.
+    pub conviction_decay_bps: u64,
+    /// Lowest conviction multiplier, in bps (10000 = 1x).
+    pub conviction_floor_bps: u64,
+
+    pub scoring_rule: ScoringRule,
+    /// Smallest error scored so far under `WinnerTakeClosest`; `u64::MAX` until a bet scores.
+    pub closest_error: u64,
+
+    pub time_bonus_curve: TimeBonusCurve,
+    pub time_bonus_max_bps: u64,
+    /// When set, place_bet/update_bet move `bet.bonus_entry_ts` to the time of the change.
+    pub reset_bonus_on_update: bool,
+
+    pub kind: PoolKind,
+    /// Outcome names for categorical pools; empty for numeric pools.
+    #[max_len(MAX_OUTCOMES, MAX_OUTCOME_LABEL_LENGTH)]
+    pub outcome_labels: Vec<String>,
+    /// Stake per outcome index, accumulated by batch_calculate_weights inside the
+    /// rollup, so it only becomes visible once the pool is resolved and committed.
+    #[max_len(MAX_OUTCOMES)]
+    pub outcome_totals: Vec<u64>,
+
+    /// Decimal exponent of numeric outcomes, declared at creation.
+    pub outcome_exponent: i32,
+    /// Reference for the hyperbolic rule's relative error; 0 means "relative to the result".
+    pub accuracy_scale: u64,
+
+    /// Set by `void_pool`; `None` otherwise.
+    pub void_reason: VoidReason,
+
+    /// Share of the pot, in bps, paid to `created_by` at finalize_weights.
+    pub creator_fee_bps: u64,
+
+    /// Referred stake whose referral share has not been paid yet. Grows at init_bet,
+    /// shrinks as referred bets are claimed.
+    pub referred_stake: u64,
+    /// Protocol-fee share held in the vault for referrers that have not been paid yet.
+    pub referral_reserve: u64,
+
+    /// Creator-funded prize held in the vault on top of the stakes. Folded into the
+    /// distributable amount at finalize_weights, or returned to the creator when
+    /// nobody scores or the pool is cancelled/voided; zero afterwards.
+    pub subsidy_amount: u64,
+
+    /// Smallest opening stake for a bet.
+    pub min_stake: u64,
+    /// Largest total stake a single bet may hold; 0 means no cap.
+    pub max_stake_per_bet: u64,
+    /// Largest `total_staked` the pool accepts; 0 means no cap.
+    pub max_pool_total: u64,
+
+    /// Share of a withdrawal, in bps, kept in the vault for the remaining bettors.
+    pub exit_penalty_bps: u64,
+
+    /// Seconds bettors have to claim once the pool is finalized, voided or cancelled.
+    pub claim_window: i64,
+    /// End of the claim window; 0 until the pool becomes claimable. After it,
+    /// `sweep_unclaimed` may empty the vault and close the pool.
+    pub claim_deadline: i64,
+
+    /// Set at creation. Instructions that move stake branch on it and take either
+    /// the token accounts or the `SolVault`.
+    pub asset_kind: AssetKind,
+
+    /// Protocol fee, in bps, copied from `Protocol` at creation and charged at
+    /// finalize_weights, so later config changes never reach existing pools.
+    pub fee_bps: u64,
+
+    /// Top-ups deposited through `add_stake` that no scored bet has credited. Kept
+    /// out of the pot at finalize_weights and refunded to their bets at claim.
+    pub pending_top_ups: u64,
+
+    /// Layout version; 0 for pools created before versioning. See `migrate_pool`.
+    pub version: u8,
+}
+
+impl Pool {
+    pub const SPACE: usize = 8 + Pool::INIT_SPACE;
+    pub const VERSION: u8 = 1;
+
+    /// Brings a pool read under an older layout up to `VERSION`. Fields added since
+    /// are zero; those where zero would change how the pool settles get the behaviour
+    /// it was created with: the live `protocol_fee_bps` it would have been charged and
+    /// the hardcoded quadratic time bonus.
+    pub fn upgrade(&mut self, protocol_fee_bps: u64) -> Result<()> {
+        require!(self.version < Self::VERSION, CustomError::AccountUpToDate);
+
+        if self.claim_window == 0 {
+            self.claim_window = DEFAULT_CLAIM_WINDOW;
+        }
+        if self.fee_bps == 0 {
+            self.fee_bps = protocol_fee_bps;
+        }
+        if self.time_bonus_curve == TimeBonusCurve::None && self.time_bonus_max_bps == 0 {
+            self.time_bonus_curve = TimeBonusCurve::Quadratic;
+            self.time_bonus_max_bps = LEGACY_TIME_BONUS_MAX_BPS;
+        }
+        // Only winner-take-closest pools track it, and `create_pool` starts it at "no bet yet".
+        if self.scoring_rule != ScoringRule::WinnerTakeClosest {
+            self.closest_error = u64::MAX;
+        }
+
+        self.version = Self::VERSION;
+        Ok(())
+    }
+
+    /// Categorical predictions and results must name one of the pool's outcomes.
+    pub fn validate_outcome(&self, outcome: u64) -> Result<()> {
+        if self.kind == PoolKind::Categorical {
+            require!(
+                (outcome as usize) < self.outcome_labels.len(),
+                CustomError::InvalidOutcomeIndex
+            );
+        }
+        Ok(())
+    }
+
+    /// Checks a point (`high == None`) or interval prediction. Intervals are only
+    /// meaningful for numeric pools and must satisfy `low <= high`.
+    pub fn validate_prediction(&self, low: u64, high: Option<u64>) -> Result<()> {
+        self.validate_outcome(low)?;
+
+        if let Some(high) = high {
+            require!(self.kind != PoolKind::Categorical, CustomError::InvalidInterval);
+            require!(
+                self.outcome_value(low) <= self.outcome_value(high),
+                CustomError::InvalidInterval
+            );
+        }
+        Ok(())
+    }
+
+    /// Decodes a stored prediction or result, honouring the pool's signedness.
+    pub fn outcome_value(&self, raw: u64) -> i128 {
+        match self.kind {
+            PoolKind::SignedNumeric => raw as i64 as i128,
+            _ => raw as i128,
+        }
+    }
+
+    /// Starts the claim window when the pool becomes claimable.
+    pub fn open_claim_window(&mut self, now: i64) {
+        self.claim_deadline = now.saturating_add(self.claim_window);
+    }
+
+    /// Voids the pool so every bet can take back its stake, and opens the claim window.
+    pub fn void(&mut self, reason: VoidReason, now: i64) {
+        self.status = PoolStatus::Voided;
+        self.void_reason = reason;
+        self.open_claim_window(now);
+    }
+
+    /// True once the claim window has run out.
+    pub fn claim_deadline_passed(&self, now: i64) -> bool {
+        self.claim_deadline > 0 && now > self.claim_deadline
+    }
+
+    /// Checks a deposit of `amount` that leaves the bet holding `bet_total`.
+    pub fn check_bet_stake(&self, amount: u64, bet_total: u64) -> Result<()> {
+        require!(
+            amount > 0 && bet_total >= self.min_stake,
+            CustomError::StakeBelowMinimum
+        );
+        require!(
+            self.max_stake_per_bet == 0 || bet_total <= self.max_stake_per_bet,
+            CustomError::StakeAboveMaximum
+        );
+        Ok(())
+    }
+
+    /// Checks that the vault can take `amount` more stake without passing `max_pool_total`.
+    pub fn check_pool_capacity(&self, amount: u64) -> Result<()> {
+        let total = self
+            .total_staked
+            .checked_add(amount)
+            .ok_or(CustomError::MathOverflow)?;
+        require!(
+            self.max_pool_total == 0 || total <= self.max_pool_total,
+            CustomError::PoolCapacityExceeded
+        );
+        Ok(())
+    }
+
+    /// Value the hyperbolic rule measures relative error against.
+    pub fn accuracy_reference(&self, result: u64) -> u64 {
+        if self.accuracy_scale > 0 {
+            self.accuracy_scale
+        } else {
+            result
+        }
+    }
 }
\ No newline at end of file
@@ -1,6 +1,10 @@
 use anchor_lang::prelude::*;
+use crate::constants::{DEFAULT_CONFIG_DELAY, DEFAULT_DISPUTE_WINDOW, DEFAULT_MIN_CLAIM_WINDOW};
+use crate::errors::CustomError;
+use crate::state::Roles;
 
 #[account]
+#[derive(InitSpace)]
 pub struct Protocol {
     pub admin: Pubkey,
     pub treasury_wallet: Pubkey,
@@ -8,8 +12,77 @@ pub struct Protocol {
     pub paused: bool,
     pub batch_settle_wait_duration: i64,
     pub total_pools: u64,
+    /// Seconds an optimistic resolution proposal stays open for disputes.
+    pub dispute_window: i64,
+    /// Bond, in the pool's stake token, posted by proposers and disputers.
+    pub resolution_bond: u64,
+    /// Key that settles disputed proposals.
+    pub arbiter: Pubkey,
+    /// Lamports a non-admin creator escrows per pool at `create_pool`.
+    pub creation_bond: u64,
+    /// Share of the protocol fee, in bps, routed to referrers of the pool's bets.
+    pub referral_fee_bps: u64,
+    /// Key proposed by `propose_admin`; default when no transfer is pending.
+    pub pending_admin: Pubkey,
+    /// Minimum seconds between queuing a timelocked config change and executing it.
+    pub config_delay: i64,
+    /// Change queued by `update_config`, applied by `execute_config_change`.
+    pub pending_config: PendingConfig,
+    /// Layout version; 0 for accounts created before versioning. See `migrate_protocol`.
+    pub version: u8,
+    /// Shortest non-zero `claim_window` a pool may be created with, so winners are
+    /// never left seconds to claim before the vault can be swept.
+    pub min_claim_window: i64,
+}
+
+/// Timelocked settings queued by `update_config`. `None` leaves a setting unchanged.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default, InitSpace)]
+pub struct PendingConfig {
+    pub treasury: Option<Pubkey>,
+    pub protocol_fee_bps: Option<u64>,
+    pub batch_settle_wait_duration: Option<i64>,
+    pub config_delay: Option<i64>,
+    /// Earliest time the change can be executed; 0 when nothing is queued.
+    pub effective_at: i64,
+}
+
+impl PendingConfig {
+    pub fn is_empty(&self) -> bool {
+        self.treasury.is_none()
+            && self.protocol_fee_bps.is_none()
+            && self.batch_settle_wait_duration.is_none()
+            && self.config_delay.is_none()
+    }
 }
 
 impl Protocol {
-    pub const BASE_LEN: usize = 8 + 32 + 32 + 8 + 1 + 8 + 8;
+    pub const SPACE: usize = 8 + Protocol::INIT_SPACE;
+    pub const VERSION: u8 = 2;
+
+    /// Brings an account read under an older layout up to `VERSION`. Fields added
+    /// since are zero; those where zero is unsafe get a default.
+    pub fn upgrade(&mut self) -> Result<()> {
+        require!(self.version < Self::VERSION, CustomError::AccountUpToDate);
+
+        if self.dispute_window == 0 {
+            self.dispute_window = DEFAULT_DISPUTE_WINDOW;
+        }
+        if self.arbiter == Pubkey::default() {
+            self.arbiter = self.admin;
+        }
+        if self.config_delay == 0 {
+            self.config_delay = DEFAULT_CONFIG_DELAY;
+        }
+        if self.min_claim_window == 0 {
+            self.min_claim_window = DEFAULT_MIN_CLAIM_WINDOW;
+        }
+
+        self.version = Self::VERSION;
+        Ok(())
+    }
+
+    /// True if `key` is the admin, or `roles` is its `Roles` account and holds `role`.
+    pub fn has_role(&self, key: Pubkey, roles: Option<&Roles>, role: u8) -> bool {
+        key == self.admin || roles.is_some_and(|roles| roles.holder == key && roles.has(role))
+    }
 }
@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+
+/// Per-referrer, per-mint referral ledger. Earned fees are held in the matching
+/// referral vault (authority = this account) until `claim_referral_fees`.
+#[account]
+pub struct ReferralStats {
+    pub referrer: Pubkey,
+    pub mint: Pubkey,
+    /// Lifetime referral fees credited to the vault.
+    pub total_earned: u64,
+    /// Lifetime referral fees withdrawn by the referrer.
+    pub total_claimed: u64,
+    /// Number of claimed bets that paid a referral share.
+    pub referred_bets: u64,
+    pub bump: u8,
+}
+
+impl ReferralStats {
+    pub const SPACE: usize = 8 + 32 + 32 + 8 + 8 + 8 + 1;
+}
@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+/// Optimistic resolution proposal for a pool. Bonds from the proposer and any
+/// disputer are escrowed in the pool's bond vault until settlement.
+#[account]
+pub struct ResolutionProposal {
+    pub pool: Pubkey,
+    pub proposer: Pubkey,
+    pub proposed_outcome: u64,
+    pub proposed_at: i64,
+    /// Snapshot of `protocol.dispute_window` at proposal time.
+    pub dispute_deadline: i64,
+    /// Net bond the vault received from the proposer; a disputer posts the same amount.
+    pub bond_amount: u64,
+
+    /// `Pubkey::default()` until the proposal is disputed.
+    pub disputer: Pubkey,
+    pub disputed_at: i64,
+
+    pub bump: u8,
+}
+
+impl ResolutionProposal {
+    pub const SPACE: usize = 8 + 32 + 32 + 8 + 8 + 8 + 8 + 32 + 8 + 1;
+}
@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+
+/// Roles granted to one wallet, as a bit set of the `ROLE_*` constants. The
+/// protocol admin implicitly holds every role and needs no account.
+#[account]
+pub struct Roles {
+    pub holder: Pubkey,
+    pub roles: u8,
+    pub bump: u8,
+}
+
+impl Roles {
+    pub const SPACE: usize = 8 + 32 + 1 + 1;
+
+    pub fn has(&self, role: u8) -> bool {
+        self.roles & role == role
+    }
+}
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+use crate::errors::CustomError;
+
+/// Lamport vault of a native SOL pool. The creator pays its rent-exempt reserve
+/// at `create_pool`; everything above the reserve is stake or subsidy, and the
+/// reserve (plus any rounding dust) goes back to the creator when the vault closes.
+#[account]
+pub struct SolVault {
+    pub pool: Pubkey,
+    pub bump: u8,
+}
+
+impl SolVault {
+    pub const SPACE: usize = 8 + 32 + 1;
+
+    /// Lamports held above the rent-exempt reserve.
+    pub fn available(info: &AccountInfo) -> Result<u64> {
+        let reserve = Rent::get()?.minimum_balance(info.data_len());
+        Ok(info.lamports().saturating_sub(reserve))
+    }
+
+    /// Pays `amount` out of the vault without ever dipping into its reserve. The
+    /// vault is owned by this program, so lamports are moved directly.
+    pub fn pay(info: &AccountInfo, to: &AccountInfo, amount: u64) -> Result<()> {
+        require!(amount <= Self::available(info)?, CustomError::InsufficientLiquidity);
+        info.sub_lamports(amount)?;
+        to.add_lamports(amount)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+/// L1 ledger of stake top-ups for one bet. `add_stake` deposits into the vault and
+/// bumps it; `update_bet` in the rollup may only credit `bet.stake` with the part of
+/// `total_amount` the bet has not consumed yet.
+#[account]
+pub struct StakeReceipt {
+    pub bet: Pubkey,
+    pub user: Pubkey,
+    pub pool: Pubkey,
+    /// Lifetime amount deposited through `add_stake`.
+    pub total_amount: u64,
+    /// Number of `add_stake` deposits; the bet records the last nonce it consumed.
+    pub nonce: u64,
+    pub bump: u8,
+}
+
+impl StakeReceipt {
+    pub const SPACE: usize = 8 + 32 + 32 + 32 + 8 + 8 + 1;
+
+    /// Deposited stake not yet credited to a bet that has consumed `credited` so far.
+    pub fn pending(&self, credited: u64) -> u64 {
+        self.total_amount.saturating_sub(credited)
+    }
+}
@@ -1,31 +1,94 @@
+use crate::constants::{BPS_DENOMINATOR, INTERVAL_ALPHA_BPS};
 use crate::errors::CustomError;
+use crate::state::{ScoringRule, TimeBonusCurve};
 use anchor_lang::prelude::*;
 
 pub const MATH_PRECISION: u128 = 1_000_000; 
 
+/// Interval score of a `[low, high]` band (Gneiting & Raftery), scaled by `alpha / 2`
+/// so it is expressed in outcome units: `alpha / 2 * width + distance outside the band`.
+/// A point bet (`low == high`) reduces to its absolute error, so point and interval
+/// bets share one pool and one scoring rule. `high < low` is treated as a point at `low`.
+pub fn calculate_interval_error(low: i128, high: i128, result: i128) -> Result<u64> {
+    let high = high.max(low);
+    let width = (high - low) as u128;
+
+    let miss = if result < low {
+        (low - result) as u128
+    } else if result > high {
+        (result - high) as u128
+    } else {
+        0
+    };
+
+    let width_penalty = width
+        .checked_mul(INTERVAL_ALPHA_BPS as u128)
+        .ok_or(CustomError::MathOverflow)?
+        .checked_div(2 * BPS_DENOMINATOR as u128)
+        .ok_or(CustomError::MathOverflow)?;
+
+    let error = width_penalty.checked_add(miss).ok_or(CustomError::MathOverflow)?;
+
+    Ok(u64::try_from(error).unwrap_or(u64::MAX))
+}
+
+/// Categorical pools score all-or-nothing: full accuracy for the winning outcome.
+pub fn calculate_categorical_score(is_winner: bool) -> u64 {
+    if is_winner {
+        MATH_PRECISION as u64
+    } else {
+        0
+    }
+}
+
+#[allow(clippy::manual_abs_diff)]
 pub fn calculate_accuracy_score(
+    rule: ScoringRule,
     prediction: u64,
     result: u64,
     max_accuracy_buffer: u64,
 ) -> Result<u64> {
-    if result == 0 {
-        return Ok(0);
-    }
-
     let diff = if prediction > result {
         prediction - result
     } else {
         result - prediction
     };
 
-    if max_accuracy_buffer > 0 && diff >= max_accuracy_buffer {
+    calculate_error_score(rule, diff, result, max_accuracy_buffer)
+}
+
+/// Maps an absolute prediction error to an accuracy score scaled by `MATH_PRECISION`.
+/// `reference` is the value the hyperbolic rule measures relative error against.
+pub fn calculate_error_score(
+    rule: ScoringRule,
+    error: u64,
+    reference: u64,
+    max_accuracy_buffer: u64,
+) -> Result<u64> {
+    if max_accuracy_buffer > 0 && error >= max_accuracy_buffer {
         return Ok(0);
     }
 
-    let error_scaled = (diff as u128)
+    match rule {
+        ScoringRule::Hyperbolic => hyperbolic_score(error, reference),
+        ScoringRule::LinearDecay => linear_decay_score(error, max_accuracy_buffer),
+        ScoringRule::ExponentialDecay { half_life } => exponential_decay_score(error, half_life),
+        ScoringRule::StepBuckets => step_bucket_score(error, max_accuracy_buffer),
+        // Every bet inside the buffer scores fully; batch_calculate_weights keeps only the closest.
+        ScoringRule::WinnerTakeClosest => Ok(MATH_PRECISION as u64),
+    }
+}
+
+/// `1 / (1 + 10 * error / reference)`. A zero reference scores 0.
+fn hyperbolic_score(error: u64, reference: u64) -> Result<u64> {
+    if reference == 0 {
+        return Ok(0);
+    }
+
+    let error_scaled = (error as u128)
         .checked_mul(MATH_PRECISION)
         .ok_or(CustomError::MathOverflow)?
-        .checked_div(result as u128)
+        .checked_div(reference as u128)
         .ok_or(CustomError::MathOverflow)?;
 
     let denominator = MATH_PRECISION
@@ -43,53 +106,158 @@ pub fn calculate_accuracy_score(
     Ok(score as u64)
 }
 
+/// `1 - error / buffer`, reaching 0 at the buffer edge.
+fn linear_decay_score(error: u64, max_accuracy_buffer: u64) -> Result<u64> {
+    if max_accuracy_buffer == 0 || error >= max_accuracy_buffer {
+        return Ok(0);
+    }
+
+    let remaining = (max_accuracy_buffer - error) as u128;
+
+    let score = remaining
+        .checked_mul(MATH_PRECISION)
+        .ok_or(CustomError::MathOverflow)?
+        .checked_div(max_accuracy_buffer as u128)
+        .ok_or(CustomError::MathOverflow)?;
+
+    Ok(score as u64)
+}
+
+/// `2^(-error / half_life)`, with linear interpolation between whole half-lives.
+fn exponential_decay_score(error: u64, half_life: u64) -> Result<u64> {
+    if half_life == 0 {
+        return Ok(if error == 0 { MATH_PRECISION as u64 } else { 0 });
+    }
+
+    let halvings = error / half_life;
+    if halvings >= 64 {
+        return Ok(0);
+    }
+
+    let base = MATH_PRECISION >> halvings;
+    let remainder = (error % half_life) as u128;
+
+    // Within a half-life the factor falls linearly from 1 to 0.5.
+    let reduction = (MATH_PRECISION / 2)
+        .checked_mul(remainder)
+        .ok_or(CustomError::MathOverflow)?
+        .checked_div(half_life as u128)
+        .ok_or(CustomError::MathOverflow)?;
+
+    let score = base
+        .checked_mul(MATH_PRECISION - reduction)
+        .ok_or(CustomError::MathOverflow)?
+        .checked_div(MATH_PRECISION)
+        .ok_or(CustomError::MathOverflow)?;
+
+    Ok(score as u64)
+}
+
+/// Absolute-error buckets over the buffer: within 1/4 scores 1, within 1/2
+/// scores 0.5, within the buffer scores 0.25.
+fn step_bucket_score(error: u64, max_accuracy_buffer: u64) -> Result<u64> {
+    if max_accuracy_buffer == 0 || error >= max_accuracy_buffer {
+        return Ok(0);
+    }
+
+    let error = error as u128;
+    let buffer = max_accuracy_buffer as u128;
+
+    let score = if error.checked_mul(4).ok_or(CustomError::MathOverflow)? <= buffer {
+        MATH_PRECISION
+    } else if error.checked_mul(2).ok_or(CustomError::MathOverflow)? <= buffer {
+        MATH_PRECISION / 2
+    } else {
+        MATH_PRECISION / 4
+    };
+
+    Ok(score as u64)
+}
+
+/// Early-entry multiplier scaled by `MATH_PRECISION`. `t` is the share of the
+/// betting window (start → cutoff) still remaining at `entry_time`; the curve maps
+/// it to an extra bonus of up to `max_bonus_bps`.
 pub fn calculate_time_bonus(
+    curve: TimeBonusCurve,
+    max_bonus_bps: u64,
     start_time: i64,
     cutoff_time: i64,
     entry_time: i64,
 ) -> Result<u64> {
-    if entry_time >= cutoff_time {
+    if entry_time >= cutoff_time || curve == TimeBonusCurve::None {
         return Ok(MATH_PRECISION as u64);
     }
 
-    let total_duration = (cutoff_time - start_time) as u128;
-    let time_from_start = (cutoff_time - entry_time) as u128;
-
+    let total_duration = cutoff_time.saturating_sub(start_time).max(0) as u128;
     if total_duration == 0 {
         return Ok(MATH_PRECISION as u64);
     }
 
-    let t_scaled = time_from_start
+    // Entries before start_time (not possible via init_bet) are clamped to t = 1.
+    let time_remaining = (cutoff_time.saturating_sub(entry_time) as u128).min(total_duration);
+
+    let t_scaled = time_remaining
         .checked_mul(MATH_PRECISION)
         .ok_or(CustomError::MathOverflow)?
         .checked_div(total_duration)
         .ok_or(CustomError::MathOverflow)?;
 
-    let t_sq_scaled = t_scaled
-        .checked_mul(t_scaled)
-        .ok_or(CustomError::MathOverflow)?
-        .checked_div(MATH_PRECISION)
-        .ok_or(CustomError::MathOverflow)?;
+    let curve_scaled = match curve {
+        TimeBonusCurve::None => 0,
+        TimeBonusCurve::Linear => t_scaled,
+        TimeBonusCurve::Quadratic => t_scaled
+            .checked_mul(t_scaled)
+            .ok_or(CustomError::MathOverflow)?
+            .checked_div(MATH_PRECISION)
+            .ok_or(CustomError::MathOverflow)?,
+        // First third of the window earns the full bonus, second third half, last third none.
+        TimeBonusCurve::Step => {
+            if t_scaled * 3 >= MATH_PRECISION * 2 {
+                MATH_PRECISION
+            } else if t_scaled * 3 >= MATH_PRECISION {
+                MATH_PRECISION / 2
+            } else {
+                0
+            }
+        }
+    };
 
-    let bonus = t_sq_scaled
-        .checked_mul(15)
+    let bonus = curve_scaled
+        .checked_mul(max_bonus_bps as u128)
         .ok_or(CustomError::MathOverflow)?
-        .checked_div(10)
+        .checked_div(BPS_DENOMINATOR as u128)
         .ok_or(CustomError::MathOverflow)?;
 
     let factor = MATH_PRECISION
         .checked_add(bonus)
         .ok_or(CustomError::MathOverflow)?;
 
-    Ok(factor as u64)
+    u64::try_from(factor).map_err(|_| CustomError::MathOverflow.into())
 }
 
-pub fn calculate_conviction_bonus(update_count: u32) -> u64 {
-    if update_count == 0 {
-        1_500_000
-    } else {
-        1_000_000
-    }
+/// Conviction multiplier scaled by `MATH_PRECISION`. `place_bet` records the first
+/// prediction as one update, so only counts above 1 are treated as revisions.
+pub fn calculate_conviction_bonus(
+    update_count: u32,
+    bonus_bps: u64,
+    decay_bps: u64,
+    floor_bps: u64,
+) -> Result<u64> {
+    let revisions = update_count.saturating_sub(1) as u64;
+
+    let multiplier_bps = BPS_DENOMINATOR
+        .checked_add(bonus_bps)
+        .ok_or(CustomError::MathOverflow)?
+        .saturating_sub(decay_bps.saturating_mul(revisions))
+        .max(floor_bps);
+
+    let factor = (multiplier_bps as u128)
+        .checked_mul(MATH_PRECISION)
+        .ok_or(CustomError::MathOverflow)?
+        .checked_div(BPS_DENOMINATOR as u128)
+        .ok_or(CustomError::MathOverflow)?;
+
+    u64::try_from(factor).map_err(|_| CustomError::MathOverflow.into())
 }
 
 pub fn calculate_weight(
@@ -112,4 +280,219 @@ pub fn calculate_weight(
         .checked_div(MATH_PRECISION).ok_or(CustomError::MathOverflow)?;
 
     Ok(final_weight)
+}
+
+
+/// Median of `values`. For an even count, the midpoint of the two middle values (rounded down).
+pub fn calculate_median(values: &mut [u64]) -> Result<u64> {
+    require!(!values.is_empty(), CustomError::InvalidCommittee);
+
+    values.sort_unstable();
+    let mid = values.len() / 2;
+
+    if values.len() % 2 == 1 {
+        return Ok(values[mid]);
+    }
+
+    let low = values[mid - 1];
+    let high = values[mid];
+
+    Ok(low + (high - low) / 2)
+}
+
+/// Distance of `value` from `reference`, in basis points of `reference`.
+pub fn calculate_deviation_bps(value: u64, reference: u64) -> Result<u64> {
+    let diff = value.abs_diff(reference);
+
+    if reference == 0 {
+        return Ok(if diff == 0 { 0 } else { u64::MAX });
+    }
+
+    let deviation = (diff as u128)
+        .checked_mul(10000)
+        .ok_or(CustomError::MathOverflow)?
+        .checked_div(reference as u128)
+        .ok_or(CustomError::MathOverflow)?;
+
+    Ok(deviation.min(u64::MAX as u128) as u64)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BUFFER: u64 = 5_000_000;
+    const RESULT: u64 = 75_780_000;
+
+    const ALL_RULES: [ScoringRule; 5] = [
+        ScoringRule::Hyperbolic,
+        ScoringRule::LinearDecay,
+        ScoringRule::ExponentialDecay { half_life: 1_000_000 },
+        ScoringRule::StepBuckets,
+        ScoringRule::WinnerTakeClosest,
+    ];
+
+    #[test]
+    fn every_rule_is_monotonically_non_increasing_in_error() {
+        for rule in ALL_RULES {
+            let mut previous = u64::MAX;
+            for step in 0..=120u64 {
+                let error = step * BUFFER / 100;
+                let score = calculate_error_score(rule, error, RESULT, BUFFER).unwrap();
+                assert!(score <= previous, "score rose at error {}", error);
+                assert!(score <= MATH_PRECISION as u64);
+                previous = score;
+            }
+        }
+    }
+
+    #[test]
+    fn every_rule_scores_zero_at_the_buffer_edge() {
+        for rule in ALL_RULES {
+            assert_eq!(calculate_error_score(rule, BUFFER, RESULT, BUFFER).unwrap(), 0);
+        }
+    }
+
+    #[test]
+    fn exact_predictions_score_fully() {
+        for rule in ALL_RULES {
+            let score = calculate_accuracy_score(rule, RESULT, RESULT, BUFFER).unwrap();
+            assert_eq!(score, MATH_PRECISION as u64);
+        }
+    }
+
+    #[test]
+    fn hyperbolic_matches_original_curve() {
+        // 1% relative error => 1 / (1 + 10 * 0.01) = 0.909090
+        let score =
+            calculate_accuracy_score(ScoringRule::Hyperbolic, 101_000_000, 100_000_000, 0).unwrap();
+        assert_eq!(score, 909_090);
+    }
+
+    #[test]
+    fn exponential_decay_halves_each_half_life() {
+        let rule = ScoringRule::ExponentialDecay { half_life: 1_000 };
+        assert_eq!(calculate_error_score(rule, 1_000, 1, 0).unwrap(), 500_000);
+        assert_eq!(calculate_error_score(rule, 2_000, 1, 0).unwrap(), 250_000);
+        assert_eq!(calculate_error_score(rule, 64_000, 1, 0).unwrap(), 0);
+    }
+
+    #[test]
+    fn step_buckets_use_quarter_and_half_buffer_boundaries() {
+        let rule = ScoringRule::StepBuckets;
+        assert_eq!(calculate_error_score(rule, BUFFER / 4, 0, BUFFER).unwrap(), 1_000_000);
+        assert_eq!(calculate_error_score(rule, BUFFER / 2, 0, BUFFER).unwrap(), 500_000);
+        assert_eq!(calculate_error_score(rule, BUFFER - 1, 0, BUFFER).unwrap(), 250_000);
+    }
+
+    #[test]
+    fn extreme_values_do_not_overflow() {
+        for rule in ALL_RULES {
+            for (prediction, result) in [(u64::MAX, 1), (0, u64::MAX), (u64::MAX, u64::MAX)] {
+                assert!(calculate_accuracy_score(rule, prediction, result, 0).is_ok());
+                assert!(calculate_accuracy_score(rule, prediction, result, u64::MAX).is_ok());
+            }
+        }
+    }
+
+    #[test]
+    fn weight_at_extreme_stake_and_maximum_multipliers_fits() {
+        let max_time_bonus = calculate_time_bonus(
+            TimeBonusCurve::Quadratic,
+            crate::constants::MAX_TIME_BONUS_BPS,
+            0,
+            100,
+            0,
+        )
+        .unwrap();
+        let max_conviction = calculate_conviction_bonus(
+            0,
+            crate::constants::MAX_CONVICTION_BONUS_BPS,
+            0,
+            0,
+        )
+        .unwrap();
+
+        let weight = calculate_weight(
+            u64::MAX,
+            MATH_PRECISION as u64,
+            max_time_bonus,
+            max_conviction,
+        )
+        .unwrap();
+
+        assert!(weight > u64::MAX as u128);
+    }
+
+    #[test]
+    fn time_bonus_curves_decrease_toward_cutoff() {
+        let curves = [
+            TimeBonusCurve::None,
+            TimeBonusCurve::Linear,
+            TimeBonusCurve::Quadratic,
+            TimeBonusCurve::Step,
+        ];
+
+        for curve in curves {
+            let mut previous = u64::MAX;
+            for entry in 0..=100 {
+                let bonus = calculate_time_bonus(curve, 15_000, 0, 100, entry).unwrap();
+                assert!(bonus <= previous);
+                assert!(bonus >= MATH_PRECISION as u64);
+                previous = bonus;
+            }
+        }
+    }
+
+    #[test]
+    fn quadratic_time_bonus_matches_original_1_5x_curve() {
+        assert_eq!(
+            calculate_time_bonus(TimeBonusCurve::Quadratic, 15_000, 0, 100, 0).unwrap(),
+            2_500_000
+        );
+        assert_eq!(
+            calculate_time_bonus(TimeBonusCurve::Quadratic, 15_000, 0, 100, 50).unwrap(),
+            1_375_000
+        );
+    }
+
+    #[test]
+    fn scaled_hyperbolic_scores_zero_and_negative_results() {
+        const SCALE: u64 = 1_000;
+
+        // Result 0 with an exact prediction scores fully once a scale replaces the result.
+        assert_eq!(
+            calculate_error_score(ScoringRule::Hyperbolic, 0, SCALE, 0).unwrap(),
+            MATH_PRECISION as u64
+        );
+
+        // -5 vs +5 is an error of 10 regardless of sign: 1 / (1 + 10 * 10 / 1000).
+        let error = (-5i64).abs_diff(5i64);
+        assert_eq!(error, 10);
+        assert_eq!(
+            calculate_error_score(ScoringRule::Hyperbolic, error, SCALE, 0).unwrap(),
+            909_090
+        );
+    }
+
+    #[test]
+    fn interval_error_matches_point_error_and_rewards_narrow_covering_bands() {
+        // A point bet is scored on its absolute error.
+        assert_eq!(calculate_interval_error(90, 90, 100).unwrap(), 10);
+
+        // A band covering the outcome only pays for its width: alpha/2 * 40 = 2.
+        assert_eq!(calculate_interval_error(80, 120, 100).unwrap(), 2);
+
+        // Narrower covering bands score better; missing the band adds the distance outside it.
+        assert!(
+            calculate_interval_error(95, 105, 100).unwrap()
+                < calculate_interval_error(80, 120, 100).unwrap()
+        );
+        assert_eq!(calculate_interval_error(110, 130, 100).unwrap(), 11);
+
+        // Signed values and inverted bounds.
+        assert_eq!(calculate_interval_error(-20, 20, 0).unwrap(), 2);
+        assert_eq!(calculate_interval_error(50, 0, 40).unwrap(), 10);
+    }
 }
\ No newline at end of file
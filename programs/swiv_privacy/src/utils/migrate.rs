@@ -0,0 +1,306 @@
+use anchor_lang::prelude::*;
+
+/// Grows `account` to at least `space` bytes, with `payer` topping up rent. The new
+/// tail is zeroed, which is how fields appended since the account was written read back.
+pub fn grow_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+) -> Result<()> {
+    if account.data_len() >= space {
+        return Ok(());
+    }
+
+    let required = Rent::get()?.minimum_balance(space);
+    let top_up = required.saturating_sub(account.lamports());
+    if top_up > 0 {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                system_program.key(),
+                anchor_lang::system_program::Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            top_up,
+        )?;
+    }
+
+    account.resize(space)?;
+    Ok(())
+}
+
+/// Reads `T` from account data, applies `upgrade` and writes it back in place.
+pub fn upgrade_account<T: AccountSerialize + AccountDeserialize>(
+    data: &mut [u8],
+    upgrade: impl FnOnce(&mut T) -> Result<()>,
+) -> Result<()> {
+    let mut value = T::try_deserialize(&mut &data[..])?;
+    upgrade(&mut value)?;
+    value.try_serialize(&mut &mut data[..])?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::{
+        DEFAULT_CLAIM_WINDOW, DEFAULT_CONFIG_DELAY, DEFAULT_DISPUTE_WINDOW, DEFAULT_MIN_CLAIM_WINDOW,
+        LEGACY_TIME_BONUS_MAX_BPS, MAX_OUTCOMES, MAX_OUTCOME_LABEL_LENGTH, MAX_TITLE_LENGTH,
+    };
+    use crate::state::{
+        AssetKind, Bet, BetStatus, Pool, PoolKind, PoolStatus, Protocol, ScoringRule,
+        TimeBonusCurve, VoidReason,
+    };
+
+    // The first deployed layouts, before any field was appended. Status enums are
+    // shared because their variants have only ever been appended too.
+
+    #[derive(AnchorSerialize)]
+    struct LegacyPool {
+        created_by: Pubkey,
+        title: String,
+        pool_id: u64,
+        stake_token_mint: Pubkey,
+        start_time: i64,
+        end_time: i64,
+        cutoff_time: i64,
+        total_staked: u64,
+        distributable_amount: u64,
+        max_accuracy_buffer: u64,
+        conviction_bonus_bps: u64,
+        resolution_result: u64,
+        resolution_ts: i64,
+        total_weight: u128,
+        total_participants: u64,
+        weights_calculated_count: u64,
+        status: PoolStatus,
+        bump: u8,
+    }
+    const LEGACY_POOL_SPACE: usize = 8 + 360;
+
+    #[derive(AnchorSerialize)]
+    struct LegacyBet {
+        user_pubkey: Pubkey,
+        pool_pubkey: Pubkey,
+        stake: u64,
+        end_timestamp: i64,
+        creation_ts: i64,
+        update_count: u32,
+        calculated_weight: u128,
+        is_weight_added: bool,
+        prediction: u64,
+        status: BetStatus,
+        bump: u8,
+    }
+    const LEGACY_BET_SPACE: usize = 250;
+
+    #[derive(AnchorSerialize)]
+    struct LegacyProtocol {
+        admin: Pubkey,
+        treasury_wallet: Pubkey,
+        protocol_fee_bps: u64,
+        paused: bool,
+        batch_settle_wait_duration: i64,
+        total_pools: u64,
+    }
+    const LEGACY_PROTOCOL_SPACE: usize = 8 + 32 + 32 + 8 + 1 + 8 + 8;
+
+    /// A `T` with every field zeroed: empty strings and vectors, first enum variants.
+    fn blank<T: AccountDeserialize + Discriminator>(space: usize) -> T {
+        let mut data = vec![0u8; space];
+        data[..8].copy_from_slice(T::DISCRIMINATOR);
+        T::try_deserialize(&mut &data[..]).unwrap()
+    }
+
+    /// Account data holding `value` as the legacy program wrote it: discriminator,
+    /// borsh fields, then zeros up to the space it allocated.
+    fn legacy_account<T: Discriminator>(value: &impl AnchorSerialize, space: usize) -> Vec<u8> {
+        let mut data = T::DISCRIMINATOR.to_vec();
+        value.serialize(&mut data).unwrap();
+        assert!(data.len() <= space);
+        data.resize(space, 0);
+        data
+    }
+
+    /// What `grow_account` followed by `upgrade_account` does to the account data.
+    fn migrate<T: AccountSerialize + AccountDeserialize>(
+        mut data: Vec<u8>,
+        space: usize,
+        upgrade: impl FnOnce(&mut T) -> Result<()>,
+    ) -> Result<T> {
+        if data.len() < space {
+            data.resize(space, 0);
+        }
+        upgrade_account::<T>(&mut data, upgrade)?;
+        T::try_deserialize(&mut &data[..])
+    }
+
+    fn is_up_to_date_error<T>(result: Result<T>) -> bool {
+        result.is_err_and(|error| error.to_string().contains("AccountUpToDate"))
+    }
+
+    #[test]
+    fn legacy_pool_migrates_and_keeps_settling_as_created() {
+        let legacy = LegacyPool {
+            created_by: Pubkey::new_unique(),
+            title: "BTC above 100k".to_string(),
+            pool_id: 3,
+            stake_token_mint: Pubkey::new_unique(),
+            start_time: 1_000,
+            end_time: 2_000,
+            cutoff_time: 1_900,
+            total_staked: 42,
+            distributable_amount: 0,
+            max_accuracy_buffer: 500,
+            conviction_bonus_bps: 5_000,
+            resolution_result: 7,
+            resolution_ts: 2_100,
+            total_weight: 1 << 70,
+            total_participants: 2,
+            weights_calculated_count: 1,
+            status: PoolStatus::Resolving,
+            bump: 254,
+        };
+        let data = legacy_account::<Pool>(&legacy, LEGACY_POOL_SPACE);
+        assert!(data.len() < Pool::SPACE);
+
+        let migrated = migrate(data, Pool::SPACE, |pool: &mut Pool| pool.upgrade(300)).unwrap();
+        assert_eq!(migrated.version, Pool::VERSION);
+        assert_eq!(migrated.created_by, legacy.created_by);
+        assert_eq!(migrated.title, legacy.title);
+        assert_eq!(migrated.pool_id, 3);
+        assert_eq!(migrated.stake_token_mint, legacy.stake_token_mint);
+        assert_eq!(migrated.cutoff_time, 1_900);
+        assert_eq!(migrated.total_staked, 42);
+        assert_eq!(migrated.conviction_bonus_bps, 5_000);
+        assert_eq!(migrated.total_weight, 1 << 70);
+        assert_eq!(migrated.weights_calculated_count, 1);
+        assert!(migrated.status == PoolStatus::Resolving);
+        assert_eq!(migrated.bump, 254);
+
+        // Legacy pools were charged the live protocol fee at finalize_weights.
+        assert_eq!(migrated.fee_bps, 300);
+        // ...and had the quadratic +1.5x early-entry bonus hardcoded.
+        assert!(migrated.time_bonus_curve == TimeBonusCurve::Quadratic);
+        assert_eq!(migrated.time_bonus_max_bps, LEGACY_TIME_BONUS_MAX_BPS);
+        assert_eq!(migrated.closest_error, u64::MAX);
+        // A zero claim window would let the vault be swept as soon as the pool settles.
+        assert_eq!(migrated.claim_window, DEFAULT_CLAIM_WINDOW);
+
+        assert!(migrated.scoring_rule == ScoringRule::Hyperbolic);
+        assert!(migrated.kind == PoolKind::Numeric);
+        assert!(migrated.asset_kind == AssetKind::Token);
+        assert!(migrated.void_reason == VoidReason::None);
+        assert!(migrated.outcome_labels.is_empty());
+        assert_eq!(migrated.pending_top_ups, 0);
+
+        let mut current = Vec::new();
+        migrated.try_serialize(&mut current).unwrap();
+        assert!(is_up_to_date_error(migrate(current, Pool::SPACE, |pool: &mut Pool| pool.upgrade(300))));
+    }
+
+    #[test]
+    fn legacy_bet_migrates_with_the_admin_as_sponsor() {
+        let admin = Pubkey::new_unique();
+        let legacy = LegacyBet {
+            user_pubkey: Pubkey::new_unique(),
+            pool_pubkey: Pubkey::new_unique(),
+            stake: 5_000_000,
+            end_timestamp: 2_000,
+            creation_ts: 1_200,
+            update_count: 1,
+            calculated_weight: 0,
+            is_weight_added: false,
+            prediction: 75,
+            status: BetStatus::Active,
+            bump: 253,
+        };
+        // The legacy allocation is larger than the current layout, so nothing grows.
+        let data = legacy_account::<Bet>(&legacy, LEGACY_BET_SPACE);
+
+        let migrated = migrate(data, Bet::SPACE, |bet: &mut Bet| bet.upgrade(admin)).unwrap();
+        assert_eq!(migrated.version, Bet::VERSION);
+        assert_eq!(migrated.user_pubkey, legacy.user_pubkey);
+        assert_eq!(migrated.pool_pubkey, legacy.pool_pubkey);
+        assert_eq!(migrated.stake, 5_000_000);
+        assert_eq!(migrated.creation_ts, 1_200);
+        assert_eq!(migrated.update_count, 1);
+        assert_eq!(migrated.prediction, 75);
+        assert!(migrated.status == BetStatus::Active);
+        assert_eq!(migrated.bump, 253);
+        assert_eq!(migrated.credited_top_ups, 0);
+        assert_eq!(migrated.referrer, Pubkey::default());
+        // init_bet only accepted `protocol.admin` as the rent payer.
+        assert_eq!(migrated.sponsor, admin);
+
+        let mut current = Vec::new();
+        migrated.try_serialize(&mut current).unwrap();
+        assert!(is_up_to_date_error(migrate(current, Bet::SPACE, |bet: &mut Bet| bet.upgrade(admin))));
+    }
+
+    #[test]
+    fn legacy_protocol_migrates() {
+        let legacy = LegacyProtocol {
+            admin: Pubkey::new_unique(),
+            treasury_wallet: Pubkey::new_unique(),
+            protocol_fee_bps: 300,
+            paused: true,
+            batch_settle_wait_duration: 60,
+            total_pools: 7,
+        };
+        let data = legacy_account::<Protocol>(&legacy, LEGACY_PROTOCOL_SPACE);
+        assert!(data.len() < Protocol::SPACE);
+
+        let migrated = migrate(data, Protocol::SPACE, Protocol::upgrade).unwrap();
+        assert_eq!(migrated.version, Protocol::VERSION);
+        assert_eq!(migrated.admin, legacy.admin);
+        assert_eq!(migrated.treasury_wallet, legacy.treasury_wallet);
+        assert_eq!(migrated.protocol_fee_bps, 300);
+        assert!(migrated.paused);
+        assert_eq!(migrated.batch_settle_wait_duration, 60);
+        assert_eq!(migrated.total_pools, 7);
+        assert_eq!(migrated.dispute_window, DEFAULT_DISPUTE_WINDOW);
+        assert_eq!(migrated.arbiter, legacy.admin);
+        assert_eq!(migrated.config_delay, DEFAULT_CONFIG_DELAY);
+        assert_eq!(migrated.pending_admin, Pubkey::default());
+        assert!(migrated.pending_config.is_empty());
+        assert_eq!(migrated.min_claim_window, DEFAULT_MIN_CLAIM_WINDOW);
+    }
+
+    #[test]
+    fn version_1_protocol_migrates_with_the_default_min_claim_window() {
+        let mut protocol: Protocol = blank(Protocol::SPACE);
+        protocol.admin = Pubkey::new_unique();
+        protocol.dispute_window = 600;
+        protocol.arbiter = Pubkey::new_unique();
+        protocol.config_delay = 0;
+        protocol.version = 1;
+
+        // Version 1 ended at `version` and allocated 8 bytes less.
+        let mut data = Vec::new();
+        protocol.try_serialize(&mut data).unwrap();
+        data.truncate(data.len() - 8);
+        data.resize(Protocol::SPACE - 8, 0);
+
+        let migrated = migrate(data, Protocol::SPACE, Protocol::upgrade).unwrap();
+        assert_eq!(migrated.version, Protocol::VERSION);
+        assert_eq!(migrated.min_claim_window, DEFAULT_MIN_CLAIM_WINDOW);
+        assert_eq!(migrated.dispute_window, 600);
+        assert_eq!(migrated.arbiter, protocol.arbiter);
+    }
+
+    #[test]
+    fn pool_space_fits_the_largest_pool() {
+        let mut pool: Pool = blank(Pool::SPACE);
+        pool.title = "t".repeat(MAX_TITLE_LENGTH);
+        pool.outcome_labels = vec!["l".repeat(MAX_OUTCOME_LABEL_LENGTH); MAX_OUTCOMES];
+        pool.outcome_totals = vec![u64::MAX; MAX_OUTCOMES];
+        pool.version = Pool::VERSION;
+
+        let mut data = Vec::new();
+        pool.try_serialize(&mut data).unwrap();
+        assert!(data.len() <= Pool::SPACE);
+    }
+}
@@ -1,3 +1,9 @@
 pub mod math;
+pub mod oracle;
+pub mod vault;
+pub mod migrate;
 
-pub use math::*;
\ No newline at end of file
+pub use math::*;
+pub use oracle::*;
+pub use vault::*;
+pub use migrate::*;
\ No newline at end of file
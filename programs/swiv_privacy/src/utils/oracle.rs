@@ -0,0 +1,128 @@
+use crate::constants::ORACLE_MAX_CONFIDENCE_BPS;
+use crate::errors::CustomError;
+use anchor_lang::prelude::*;
+
+/// Layout constants for a Pyth (v2) price account. Only the fields needed for
+/// resolution are read, so the account is parsed by offset instead of pulling in the SDK.
+const PYTH_MAGIC: u32 = 0xa1b2_c3d4;
+const PYTH_VERSION_2: u32 = 2;
+const PYTH_ACCOUNT_TYPE_PRICE: u32 = 3;
+const PYTH_STATUS_TRADING: u32 = 1;
+
+const OFFSET_MAGIC: usize = 0;
+const OFFSET_VERSION: usize = 4;
+const OFFSET_ACCOUNT_TYPE: usize = 8;
+const OFFSET_EXPO: usize = 20;
+const OFFSET_TIMESTAMP: usize = 96;
+const OFFSET_AGG_PRICE: usize = 208;
+const OFFSET_AGG_CONF: usize = 216;
+const OFFSET_AGG_STATUS: usize = 224;
+pub const PYTH_PRICE_MIN_LEN: usize = 240;
+
+pub struct OraclePrice {
+    pub price: i64,
+    pub conf: u64,
+    pub expo: i32,
+    pub publish_time: i64,
+}
+
+fn read_bytes<const N: usize>(data: &[u8], offset: usize) -> Result<[u8; N]> {
+    data.get(offset..offset + N)
+        .and_then(|slice| slice.try_into().ok())
+        .ok_or_else(|| CustomError::InvalidOracleAccount.into())
+}
+
+pub fn parse_pyth_price(data: &[u8]) -> Result<OraclePrice> {
+    require!(data.len() >= PYTH_PRICE_MIN_LEN, CustomError::InvalidOracleAccount);
+
+    let magic = u32::from_le_bytes(read_bytes(data, OFFSET_MAGIC)?);
+    let version = u32::from_le_bytes(read_bytes(data, OFFSET_VERSION)?);
+    let account_type = u32::from_le_bytes(read_bytes(data, OFFSET_ACCOUNT_TYPE)?);
+
+    require!(
+        magic == PYTH_MAGIC
+            && version == PYTH_VERSION_2
+            && account_type == PYTH_ACCOUNT_TYPE_PRICE,
+        CustomError::InvalidOracleAccount
+    );
+
+    let status = u32::from_le_bytes(read_bytes(data, OFFSET_AGG_STATUS)?);
+    require!(status == PYTH_STATUS_TRADING, CustomError::InvalidOraclePrice);
+
+    Ok(OraclePrice {
+        price: i64::from_le_bytes(read_bytes(data, OFFSET_AGG_PRICE)?),
+        conf: u64::from_le_bytes(read_bytes(data, OFFSET_AGG_CONF)?),
+        expo: i32::from_le_bytes(read_bytes(data, OFFSET_EXPO)?),
+        publish_time: i64::from_le_bytes(read_bytes(data, OFFSET_TIMESTAMP)?),
+    })
+}
+
+/// Validates that the price was published within `max_staleness` after `end_time`
+/// and its confidence, then rescales it from its own exponent to the pool's
+/// fixed-point exponent (truncating any extra precision). Bounding the publish time
+/// by `end_time` rather than the current time keeps a late crank from settling the
+/// pool on a price from long after the event.
+pub fn oracle_price_to_outcome(
+    oracle: &OraclePrice,
+    target_exponent: i32,
+    end_time: i64,
+    max_staleness: i64,
+) -> Result<u64> {
+    require!(oracle.price > 0, CustomError::InvalidOraclePrice);
+    require!(
+        oracle.publish_time >= end_time
+            && oracle.publish_time <= end_time.saturating_add(max_staleness),
+        CustomError::OraclePriceStale
+    );
+
+    let price = oracle.price as u128;
+
+    let max_conf = price
+        .checked_mul(ORACLE_MAX_CONFIDENCE_BPS as u128)
+        .ok_or(CustomError::MathOverflow)?
+        .checked_div(10000)
+        .ok_or(CustomError::MathOverflow)?;
+    require!((oracle.conf as u128) <= max_conf, CustomError::OracleConfidenceTooWide);
+
+    let shift = oracle.expo.checked_sub(target_exponent).ok_or(CustomError::MathOverflow)?;
+    let factor = 10u128
+        .checked_pow(shift.unsigned_abs())
+        .ok_or(CustomError::MathOverflow)?;
+
+    let outcome = if shift >= 0 {
+        price.checked_mul(factor).ok_or(CustomError::MathOverflow)?
+    } else {
+        price.checked_div(factor).ok_or(CustomError::MathOverflow)?
+    };
+
+    u64::try_from(outcome).map_err(|_| CustomError::MathOverflow.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const END_TIME: i64 = 1_700_000_000;
+    const MAX_STALENESS: i64 = 60;
+
+    fn price_at(publish_time: i64) -> OraclePrice {
+        OraclePrice { price: 7_578_000_000, conf: 1_000_000, expo: -8, publish_time }
+    }
+
+    #[test]
+    fn accepts_prices_published_within_the_staleness_window() {
+        for publish_time in [END_TIME, END_TIME + MAX_STALENESS] {
+            let outcome = oracle_price_to_outcome(&price_at(publish_time), -6, END_TIME, MAX_STALENESS);
+            assert_eq!(outcome.unwrap(), 75_780_000);
+        }
+    }
+
+    #[test]
+    fn rejects_prices_published_before_end_time_or_well_after_it() {
+        let a_year_later = END_TIME + 365 * 24 * 60 * 60;
+        for publish_time in [END_TIME - 1, END_TIME + MAX_STALENESS + 1, a_year_later] {
+            let outcome = oracle_price_to_outcome(&price_at(publish_time), -6, END_TIME, MAX_STALENESS);
+            assert!(outcome.is_err());
+        }
+    }
+}
@@ -0,0 +1,318 @@
+use crate::constants::{DISPUTE_TIMEOUT, SEED_POOL};
+use crate::errors::CustomError;
+use crate::events::DisputeBondsRefunded;
+use crate::state::{AssetKind, Pool, ResolutionProposal, SolVault};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::spl_token_2022::{
+    self,
+    extension::{
+        default_account_state::DefaultAccountState, transfer_fee::TransferFeeAmount, BaseStateWithExtensions,
+        ExtensionType, StateWithExtensions,
+    },
+    state::AccountState,
+};
+use anchor_spl::token_interface::{
+    self, HarvestWithheldTokensToMint, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+/// Where a pool holds its stake, resolved from the optional vault accounts an
+/// instruction received, so payouts don't have to branch on the asset kind.
+pub enum PoolVault<'a, 'info> {
+    Token {
+        vault: &'a InterfaceAccount<'info, TokenAccount>,
+        mint: &'a InterfaceAccount<'info, Mint>,
+        token_program: &'a Interface<'info, TokenInterface>,
+    },
+    Native {
+        vault: &'a Account<'info, SolVault>,
+    },
+}
+
+impl<'a, 'info> PoolVault<'a, 'info> {
+    /// Picks the accounts matching `pool.asset_kind`; the others are ignored.
+    pub fn resolve(
+        pool: &Pool,
+        pool_vault: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+        sol_vault: Option<&'a Account<'info, SolVault>>,
+        token_mint: Option<&'a InterfaceAccount<'info, Mint>>,
+        token_program: Option<&'a Interface<'info, TokenInterface>>,
+    ) -> Result<Self> {
+        match pool.asset_kind {
+            AssetKind::Token => {
+                let (Some(vault), Some(mint), Some(token_program)) = (pool_vault, token_mint, token_program) else {
+                    return err!(CustomError::AssetAccountsMismatch);
+                };
+                Ok(Self::Token { vault, mint, token_program })
+            }
+            AssetKind::Native => {
+                let Some(vault) = sol_vault else {
+                    return err!(CustomError::AssetAccountsMismatch);
+                };
+                Ok(Self::Native { vault })
+            }
+        }
+    }
+
+    /// Current stake and subsidy held by the vault. Native vaults exclude their
+    /// rent-exempt reserve.
+    pub fn balance(&self) -> Result<u64> {
+        match self {
+            Self::Token { vault, .. } => {
+                let info = vault.to_account_info();
+                let data = info.try_borrow_data()?;
+                Ok(TokenAccount::try_deserialize(&mut &data[..])?.amount)
+            }
+            Self::Native { vault } => SolVault::available(&vault.to_account_info()),
+        }
+    }
+
+    /// Pays `amount` to `token_account` for token pools or to `wallet` for native
+    /// pools; the recipient matching the pool's asset kind must be present.
+    pub fn pay(
+        &self,
+        pool: &Account<'info, Pool>,
+        token_account: Option<AccountInfo<'info>>,
+        wallet: Option<AccountInfo<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+        match self {
+            Self::Token { vault, mint, token_program } => {
+                let Some(token_account) = token_account else {
+                    return err!(CustomError::AssetAccountsMismatch);
+                };
+
+                let created_by_bytes = pool.created_by.as_ref();
+                let pool_id_bytes = pool.pool_id.to_le_bytes();
+                let bump = pool.bump;
+                let seeds = &[SEED_POOL, created_by_bytes, &pool_id_bytes, &[bump]];
+                let signer = &[&seeds[..]];
+
+                token_interface::transfer_checked(
+                    CpiContext::new_with_signer(
+                        token_program.key(),
+                        TransferChecked {
+                            from: vault.to_account_info(),
+                            mint: mint.to_account_info(),
+                            to: token_account,
+                            authority: pool.to_account_info(),
+                        },
+                        signer,
+                    ),
+                    amount,
+                    mint.decimals,
+                )
+            }
+            Self::Native { vault } => {
+                let Some(wallet) = wallet else {
+                    return err!(CustomError::AssetAccountsMismatch);
+                };
+                SolVault::pay(&vault.to_account_info(), &wallet, amount)
+            }
+        }
+    }
+
+    /// Closes the vault, returning its rent to `destination`. Token vaults must be
+    /// empty, and transfer fees withheld on them are harvested to the mint first;
+    /// native vaults hand over their reserve along with any rounding dust.
+    pub fn close(&self, pool: &Account<'info, Pool>, destination: AccountInfo<'info>) -> Result<()> {
+        match self {
+            Self::Token { vault, mint, token_program } => {
+                harvest_withheld_fees(
+                    vault.to_account_info(),
+                    mint.to_account_info(),
+                    token_program.to_account_info(),
+                )?;
+
+                let created_by_bytes = pool.created_by.as_ref();
+                let pool_id_bytes = pool.pool_id.to_le_bytes();
+                let bump = pool.bump;
+                let seeds = &[SEED_POOL, created_by_bytes, &pool_id_bytes, &[bump]];
+                let signer = &[&seeds[..]];
+
+                token_interface::close_account(CpiContext::new_with_signer(
+                    token_program.key(),
+                    token_interface::CloseAccount {
+                        account: vault.to_account_info(),
+                        destination,
+                        authority: pool.to_account_info(),
+                    },
+                    signer,
+                ))
+            }
+            Self::Native { vault } => vault.close(destination),
+        }
+    }
+}
+
+/// The escrow of a disputed proposal, resolved from the optional accounts of the
+/// instructions that can void a pool whose arbiter never ruled.
+pub struct DisputeBonds<'a, 'info> {
+    proposal: &'a Account<'info, ResolutionProposal>,
+    bond_vault: &'a InterfaceAccount<'info, TokenAccount>,
+    proposer: AccountInfo<'info>,
+    proposer_token_account: &'a InterfaceAccount<'info, TokenAccount>,
+    disputer_token_account: &'a InterfaceAccount<'info, TokenAccount>,
+}
+
+impl<'a, 'info> DisputeBonds<'a, 'info> {
+    /// Checks that every account is present and belongs to the proposal, and that
+    /// the arbiter has had `DISPUTE_TIMEOUT` to rule since the dispute was raised.
+    pub fn resolve(
+        proposal: Option<&'a Account<'info, ResolutionProposal>>,
+        bond_vault: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+        proposer: Option<AccountInfo<'info>>,
+        proposer_token_account: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+        disputer_token_account: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+        now: i64,
+    ) -> Result<Self> {
+        let (
+            Some(proposal),
+            Some(bond_vault),
+            Some(proposer),
+            Some(proposer_token_account),
+            Some(disputer_token_account),
+        ) = (proposal, bond_vault, proposer, proposer_token_account, disputer_token_account)
+        else {
+            return err!(CustomError::DisputeAccountsMissing);
+        };
+
+        require_keys_eq!(proposer.key(), proposal.proposer, CustomError::Unauthorized);
+        require_keys_eq!(proposer_token_account.owner, proposal.proposer, CustomError::Unauthorized);
+        require_keys_eq!(disputer_token_account.owner, proposal.disputer, CustomError::Unauthorized);
+        require!(
+            now >= proposal.disputed_at.saturating_add(DISPUTE_TIMEOUT),
+            CustomError::DisputeTimeoutNotReached
+        );
+
+        Ok(Self { proposal, bond_vault, proposer, proposer_token_account, disputer_token_account })
+    }
+
+    /// Returns each side the net bond the vault received from it, then closes the
+    /// bond vault and the proposal, whose rent the proposer paid.
+    pub fn refund(
+        &self,
+        pool: &Account<'info, Pool>,
+        mint: &InterfaceAccount<'info, Mint>,
+        token_program: &Interface<'info, TokenInterface>,
+    ) -> Result<()> {
+        let proposer_amount = self.proposal.bond_amount;
+        let disputer_amount = self
+            .bond_vault
+            .amount
+            .checked_sub(proposer_amount)
+            .ok_or(CustomError::MathOverflow)?;
+
+        let created_by_bytes = pool.created_by.as_ref();
+        let pool_id_bytes = pool.pool_id.to_le_bytes();
+        let bump = pool.bump;
+        let seeds = &[SEED_POOL, created_by_bytes, &pool_id_bytes, &[bump]];
+        let signer = &[&seeds[..]];
+
+        for (to, amount) in [
+            (self.proposer_token_account, proposer_amount),
+            (self.disputer_token_account, disputer_amount),
+        ] {
+            if amount == 0 {
+                continue;
+            }
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    token_program.key(),
+                    TransferChecked {
+                        from: self.bond_vault.to_account_info(),
+                        mint: mint.to_account_info(),
+                        to: to.to_account_info(),
+                        authority: pool.to_account_info(),
+                    },
+                    signer,
+                ),
+                amount,
+                mint.decimals,
+            )?;
+        }
+
+        harvest_withheld_fees(
+            self.bond_vault.to_account_info(),
+            mint.to_account_info(),
+            token_program.to_account_info(),
+        )?;
+        token_interface::close_account(CpiContext::new_with_signer(
+            token_program.key(),
+            token_interface::CloseAccount {
+                account: self.bond_vault.to_account_info(),
+                destination: self.proposer.clone(),
+                authority: pool.to_account_info(),
+            },
+            signer,
+        ))?;
+        self.proposal.close(self.proposer.clone())?;
+
+        emit!(DisputeBondsRefunded {
+            pool: pool.key(),
+            proposer_amount,
+            disputer_amount,
+        });
+
+        Ok(())
+    }
+}
+
+/// Moves transfer fees withheld on a Token-2022 `account` into its `mint`, which
+/// must be writable. Token-2022 refuses to close an account still holding them;
+/// legacy token accounts and accounts without withheld fees are left alone.
+pub fn harvest_withheld_fees<'info>(
+    account: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+) -> Result<()> {
+    if token_program.key() != spl_token_2022::ID {
+        return Ok(());
+    }
+
+    let withheld = {
+        let data = account.try_borrow_data()?;
+        let state = StateWithExtensions::<spl_token_2022::state::Account>::unpack(&data)?;
+        state
+            .get_extension::<TransferFeeAmount>()
+            .map_or(0, |fees| u64::from(fees.withheld_amount))
+    };
+    if withheld == 0 {
+        return Ok(());
+    }
+
+    token_interface::harvest_withheld_tokens_to_mint(
+        CpiContext::new(
+            token_program.key(),
+            HarvestWithheldTokensToMint { token_program_id: token_program, mint },
+        ),
+        vec![account],
+    )
+}
+
+/// Rejects Token-2022 mints whose extensions would let someone other than the
+/// pool move or lock its vault: transfer hooks, permanent delegates,
+/// non-transferable tokens and accounts frozen by default. Transfer fees are
+/// supported.
+pub fn require_supported_mint(mint: &InterfaceAccount<Mint>) -> Result<()> {
+    let info = mint.to_account_info();
+    if *info.owner != spl_token_2022::ID {
+        return Ok(());
+    }
+
+    let data = info.try_borrow_data()?;
+    let state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+    for extension in state.get_extension_types()? {
+        let supported = match extension {
+            ExtensionType::TransferHook | ExtensionType::PermanentDelegate | ExtensionType::NonTransferable => false,
+            ExtensionType::DefaultAccountState => {
+                state.get_extension::<DefaultAccountState>()?.state != AccountState::Frozen as u8
+            }
+            _ => true,
+        };
+        require!(supported, CustomError::UnsupportedMintExtension);
+    }
+    Ok(())
+}
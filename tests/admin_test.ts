@@ -1,9 +1,10 @@
 import * as anchor from "@anchor-lang/core";
 import { Program } from "@anchor-lang/core";
 import { SwivPrivacy } from "../target/types/swiv_privacy";
-import { SEED_PROTOCOL } from "./utils";
-import { PublicKey, SystemProgram } from "@solana/web3.js";
+import { SEED_PROTOCOL, sleep } from "./utils";
+import { PublicKey, SystemProgram, Keypair } from "@solana/web3.js";
 import { createMint } from "@solana/spl-token";
+import { expect } from "chai";
 
 describe("1. Setup & Admin", () => {
   const provider = anchor.AnchorProvider.env();
@@ -21,7 +22,7 @@ describe("1. Setup & Admin", () => {
 
     if (!existingProtocol) {
       await program.methods
-        .initializeProtocol(new anchor.BN(300))
+        .initializeProtocol(new anchor.BN(300), new anchor.BN(0))
         .accountsPartial({
           admin: admin.publicKey,
           treasuryWallet: admin.publicKey,
@@ -31,14 +32,135 @@ describe("1. Setup & Admin", () => {
       console.log("    ✅ Protocol Initialized");
     } else {
       await program.methods
-        .updateConfig(null, new anchor.BN(300), null)
+        .updateConfig(null, new anchor.BN(300), null, null, null, null, null, null, null, null)
         .accountsPartial({
           admin: admin.publicKey,
+          roles: null,
           protocol: configPda,
           systemProgram: SystemProgram.programId,
         })
         .rpc();
+      await program.methods
+        .executeConfigChange()
+        .accountsPartial({ executor: admin.publicKey, protocol: configPda })
+        .rpc();
       console.log("    ✅ Protocol Config Updated");
     }
   });
+
+  it("Two-step admin transfer", async () => {
+    const [configPda] = PublicKey.findProgramAddressSync(
+      [SEED_PROTOCOL],
+      program.programId
+    );
+    const next = Keypair.generate();
+
+    const propose = (from: Keypair, to: PublicKey) =>
+      program.methods
+        .proposeAdmin(to)
+        .accountsPartial({ admin: from.publicKey, protocol: configPda })
+        .signers([from])
+        .rpc();
+    const accept = (signer: Keypair) =>
+      program.methods
+        .acceptAdmin()
+        .accountsPartial({ pendingAdmin: signer.publicKey, protocol: configPda })
+        .signers([signer])
+        .rpc();
+
+    await propose(admin, next.publicKey);
+    let protocol = await program.account.protocol.fetch(configPda);
+    expect(protocol.admin.toBase58()).to.equal(admin.publicKey.toBase58());
+    expect(protocol.pendingAdmin.toBase58()).to.equal(next.publicKey.toBase58());
+
+    await program.methods
+      .cancelAdminTransfer()
+      .accountsPartial({ admin: admin.publicKey, protocol: configPda })
+      .rpc();
+    try {
+      await accept(next);
+      throw new Error("Should have failed with NoPendingAdmin");
+    } catch (e: any) {
+      if (!e.message.includes("NoPendingAdmin")) throw e;
+    }
+
+    // Hand over and back so later suites still run as the provider wallet.
+    await propose(admin, next.publicKey);
+    await accept(next);
+    protocol = await program.account.protocol.fetch(configPda);
+    expect(protocol.admin.toBase58()).to.equal(next.publicKey.toBase58());
+    expect(protocol.pendingAdmin.toBase58()).to.equal(PublicKey.default.toBase58());
+
+    await propose(next, admin.publicKey);
+    await accept(admin);
+    protocol = await program.account.protocol.fetch(configPda);
+    expect(protocol.admin.toBase58()).to.equal(admin.publicKey.toBase58());
+  });
+
+  it("Timelocked config changes", async () => {
+    const [configPda] = PublicKey.findProgramAddressSync(
+      [SEED_PROTOCOL],
+      program.programId
+    );
+    const DELAY = 3;
+
+    const queue = (fee: anchor.BN | null, delay: anchor.BN | null) =>
+      program.methods
+        .updateConfig(null, fee, null, null, null, null, null, null, delay, null)
+        .accountsPartial({
+          admin: admin.publicKey,
+          roles: null,
+          protocol: configPda,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    const execute = () =>
+      program.methods
+        .executeConfigChange()
+        .accountsPartial({ executor: admin.publicKey, protocol: configPda })
+        .rpc();
+
+    await queue(null, new anchor.BN(DELAY));
+    await execute();
+    let protocol = await program.account.protocol.fetch(configPda);
+    expect(protocol.configDelay.toNumber()).to.equal(DELAY);
+
+    // A queued fee does not apply until the delay has passed.
+    await queue(new anchor.BN(500), null);
+    protocol = await program.account.protocol.fetch(configPda);
+    expect(protocol.protocolFeeBps.toNumber()).to.equal(300);
+    expect(protocol.pendingConfig.protocolFeeBps.toNumber()).to.equal(500);
+    try {
+      await execute();
+      throw new Error("Should have failed with ConfigChangeNotReady");
+    } catch (e: any) {
+      if (!e.message.includes("ConfigChangeNotReady")) throw e;
+    }
+
+    // The pending change can't be replaced, only cancelled by the admin.
+    try {
+      await queue(null, new anchor.BN(0));
+      throw new Error("Should have failed with ConfigChangePending");
+    } catch (e: any) {
+      if (!e.message.includes("ConfigChangePending")) throw e;
+    }
+    protocol = await program.account.protocol.fetch(configPda);
+    expect(protocol.pendingConfig.protocolFeeBps.toNumber()).to.equal(500);
+    expect(protocol.pendingConfig.configDelay).to.be.null;
+
+    await program.methods
+      .cancelConfigChange()
+      .accountsPartial({ admin: admin.publicKey, protocol: configPda })
+      .rpc();
+    protocol = await program.account.protocol.fetch(configPda);
+    expect(protocol.pendingConfig.protocolFeeBps).to.be.null;
+
+    // Restore the zero delay the other suites rely on.
+    await queue(null, new anchor.BN(0));
+    await sleep((DELAY + 1) * 1000);
+    await execute();
+    protocol = await program.account.protocol.fetch(configPda);
+    expect(protocol.configDelay.toNumber()).to.equal(0);
+    expect(protocol.protocolFeeBps.toNumber()).to.equal(300);
+  });
 });
\ No newline at end of file
@@ -0,0 +1,48 @@
+import * as anchor from "@anchor-lang/core";
+import { Program } from "@anchor-lang/core";
+import { SwivPrivacy } from "../target/types/swiv_privacy";
+import { PublicKey } from "@solana/web3.js";
+import { createMint } from "@solana/spl-token";
+import { expect } from "chai";
+import { createPool, expectError } from "./utils";
+
+describe("Categorical Pools", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = anchor.workspace.SwivPrivacy as Program<SwivPrivacy>;
+  const admin = (provider.wallet as anchor.Wallet).payer;
+
+  const endpoint = provider.connection.rpcEndpoint;
+  const isLocalnet = endpoint.includes("localhost") || endpoint.includes("127.0.0.1");
+
+  let mint: PublicKey;
+
+  const createCategoricalPool = async (title: string, labels: string[]) =>
+    (
+      await createPool(program, {
+        title,
+        mint,
+        maxAccuracyBuffer: 0,
+        outcome: { kind: { categorical: {} }, labels, exponent: 0, accuracyScale: new anchor.BN(0) },
+      })
+    ).pool;
+
+  before(async function () {
+    if (!isLocalnet) this.skip();
+
+    mint = await createMint(provider.connection, admin, admin.publicKey, null, 6);
+  });
+
+  it("stores outcome labels and zeroed per-outcome totals", async () => {
+    const poolPda = await createCategoricalPool("Who wins?", ["Home", "Draw", "Away"]);
+
+    const pool = await program.account.pool.fetch(poolPda);
+    expect(pool.kind).to.deep.equal({ categorical: {} });
+    expect(pool.outcomeLabels).to.deep.equal(["Home", "Draw", "Away"]);
+    expect(pool.outcomeTotals.map((t) => t.toNumber())).to.deep.equal([0, 0, 0]);
+  });
+
+  it("rejects a categorical pool with a single outcome", async () => {
+    await expectError(() => createCategoricalPool("Only one", ["Yes"]), "InvalidOutcomeLabels");
+  });
+});
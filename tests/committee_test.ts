@@ -0,0 +1,135 @@
+import * as anchor from "@anchor-lang/core";
+import { Program } from "@anchor-lang/core";
+import { SwivPrivacy } from "../target/types/swiv_privacy";
+import { PublicKey, SystemProgram, Keypair } from "@solana/web3.js";
+import { createMint } from "@solana/spl-token";
+import { expect } from "chai";
+import { SEED_PROTOCOL, createPool, expectError, pdaOf, sleep } from "./utils";
+
+const SEED_COMMITTEE = Buffer.from("committee");
+const SEED_COMMITTEE_REPORTS = Buffer.from("committee_reports");
+
+describe("Committee Resolution", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = anchor.workspace.SwivPrivacy as Program<SwivPrivacy>;
+  const admin = (provider.wallet as anchor.Wallet).payer;
+
+  const endpoint = provider.connection.rpcEndpoint;
+  const isLocalnet = endpoint.includes("localhost") || endpoint.includes("127.0.0.1");
+
+  const [protocolPda] = PublicKey.findProgramAddressSync([SEED_PROTOCOL], program.programId);
+
+  // 10% of the median; a report of 150 against a median of 102 is an outlier.
+  const OUTLIER_BPS = 1000;
+  const reporters = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+  const outsider = Keypair.generate();
+
+  let committeePda: PublicKey;
+  let poolPda: PublicKey;
+  let reportsPda: PublicKey;
+  let endTime: number;
+
+  const submit = (reporter: Keypair, outcome: number) =>
+    program.methods
+      .submitReport(new anchor.BN(outcome))
+      .accountsPartial({
+        reporter: reporter.publicKey,
+        pool: poolPda,
+        committee: committeePda,
+        reports: reportsPda,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([reporter])
+      .rpc();
+
+  before(async function () {
+    if (!isLocalnet) this.skip();
+
+    for (const kp of [...reporters, outsider]) {
+      const sig = await provider.connection.requestAirdrop(kp.publicKey, 1e9);
+      await provider.connection.confirmTransaction(sig);
+    }
+
+    const committeeId = new anchor.BN(Date.now());
+    [committeePda] = PublicKey.findProgramAddressSync(
+      [SEED_COMMITTEE, committeeId.toArrayLike(Buffer, "le", 8)],
+      program.programId,
+    );
+    await program.methods
+      .createCommittee(
+        committeeId,
+        reporters.map((kp) => kp.publicKey),
+        reporters.length,
+        new anchor.BN(OUTLIER_BPS),
+      )
+      .accountsPartial({
+        admin: admin.publicKey,
+        protocol: protocolPda,
+        committee: committeePda,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const mint = await createMint(provider.connection, admin, admin.publicKey, null, 6);
+    ({ pool: poolPda, end: endTime } = await createPool(program, {
+      title: "Committee Pool",
+      mint,
+      end: Math.floor(Date.now() / 1000) + 10,
+      outcome: { kind: { numeric: {} }, labels: [], exponent: 0, accuracyScale: new anchor.BN(0) },
+      resolverCommittee: committeePda,
+    }));
+    reportsPda = pdaOf(program, [SEED_COMMITTEE_REPORTS, poolPda.toBuffer()]);
+  });
+
+  it("rejects reports before end_time", async () => {
+    await expectError(() => submit(reporters[0], 100), "DurationTooShort");
+  });
+
+  it("rejects reports from outside the committee", async () => {
+    const waitTime = (endTime - Math.floor(Date.now() / 1000) + 2) * 1000;
+    if (waitTime > 0) await sleep(waitTime);
+
+    await expectError(() => submit(outsider, 100), "NotCommitteeReporter");
+  });
+
+  it("records reports below the threshold without resolving", async () => {
+    await submit(reporters[0], 100);
+    await submit(reporters[1], 150);
+
+    await expectError(() => submit(reporters[1], 101), "DuplicateReport");
+
+    const reports = await program.account.committeeReports.fetch(reportsPda);
+    expect(reports.reports.length).to.equal(2);
+
+    const pool = await program.account.pool.fetch(poolPda);
+    expect(pool.status).to.not.have.property("resolving");
+  });
+
+  it("resolves to the median at the threshold and flags outliers", async () => {
+    const sig = await submit(reporters[2], 102);
+
+    const pool = await program.account.pool.fetch(poolPda);
+    expect(pool.status).to.have.property("resolving");
+    expect(pool.resolutionResult.toNumber()).to.equal(102);
+
+    const tx = await provider.connection.getTransaction(sig, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const parser = new anchor.EventParser(program.programId, program.coder);
+    const events = [...parser.parseLogs(tx!.meta!.logMessages!)];
+
+    const outliers = events.filter((e) => e.name === "outlierReportFlagged");
+    expect(outliers.length).to.equal(1);
+    expect(outliers[0].data.reporter.toBase58()).to.equal(reporters[1].publicKey.toBase58());
+    expect(outliers[0].data.medianOutcome.toNumber()).to.equal(102);
+
+    const resolved = events.find((e) => e.name === "committeeResolved");
+    expect(resolved!.data.reportCount).to.equal(3);
+  });
+
+  it("rejects reports once the pool is resolving", async () => {
+    await expectError(() => submit(reporters[0], 100), "AlreadyResolved");
+  });
+});
@@ -0,0 +1,93 @@
+import * as anchor from "@anchor-lang/core";
+import { Program } from "@anchor-lang/core";
+import { SwivPrivacy } from "../target/types/swiv_privacy";
+import { PublicKey, Keypair, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { createMint } from "@solana/spl-token";
+import { expect } from "chai";
+import { SEED_CREATOR_BOND, SEED_PROTOCOL, createPool, expectError, pdaOf } from "./utils";
+
+describe("Permissionless Pools & Creator Bonds", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = anchor.workspace.SwivPrivacy as Program<SwivPrivacy>;
+  const admin = (provider.wallet as anchor.Wallet).payer;
+
+  const endpoint = provider.connection.rpcEndpoint;
+  const isLocalnet = endpoint.includes("localhost") || endpoint.includes("127.0.0.1");
+
+  const [protocolPda] = PublicKey.findProgramAddressSync([SEED_PROTOCOL], program.programId);
+
+  const BOND = new anchor.BN(LAMPORTS_PER_SOL / 10);
+  const creator = Keypair.generate();
+
+  let poolPda: PublicKey;
+  let bondPda: PublicKey;
+
+  before(async function () {
+    if (!isLocalnet) this.skip();
+
+    const sig = await provider.connection.requestAirdrop(creator.publicKey, 2 * LAMPORTS_PER_SOL);
+    await provider.connection.confirmTransaction(sig);
+
+    await program.methods
+      .updateConfig(null, null, null, null, null, null, BOND, null, null, null)
+      .accountsPartial({ admin: admin.publicKey, protocol: protocolPda, roles: null })
+      .rpc();
+  });
+
+  after(async function () {
+    if (!isLocalnet) return;
+    await program.methods
+      .updateConfig(null, null, null, null, null, null, new anchor.BN(0), null, null, null)
+      .accountsPartial({ admin: admin.publicKey, protocol: protocolPda, roles: null })
+      .rpc();
+  });
+
+  it("lets any wallet create a pool by escrowing the creation bond", async () => {
+    const mint = await createMint(provider.connection, admin, admin.publicKey, null, 6);
+    ({ pool: poolPda } = await createPool(program, { title: "Community Pool", mint, creator }));
+    bondPda = pdaOf(program, [SEED_CREATOR_BOND, poolPda.toBuffer()]);
+
+    const bond = await program.account.creatorBond.fetch(bondPda);
+    expect(bond.creator.toBase58()).to.equal(creator.publicKey.toBase58());
+    expect(bond.amount.toString()).to.equal(BOND.toString());
+  });
+
+  it("rejects releasing the bond before the pool is resolved", async () => {
+    await expectError(
+      () =>
+        program.methods
+          .releaseCreatorBond()
+          .accountsPartial({
+            caller: admin.publicKey,
+            pool: poolPda,
+            creatorBond: bondPda,
+            creator: creator.publicKey,
+          })
+          .rpc(),
+      "SettlementTooEarly",
+    );
+  });
+
+  it("lets the admin slash the bond to the treasury", async () => {
+    const protocol = await program.account.protocol.fetch(protocolPda);
+    const treasuryBefore = await provider.connection.getBalance(protocol.treasuryWallet);
+
+    await program.methods
+      .moderateCreatorBond({ slash: {} })
+      .accountsPartial({
+        admin: admin.publicKey,
+        protocol: protocolPda,
+        creatorBond: bondPda,
+        creator: creator.publicKey,
+        treasury: protocol.treasuryWallet,
+      })
+      .rpc();
+
+    const treasuryAfter = await provider.connection.getBalance(protocol.treasuryWallet);
+    if (!protocol.treasuryWallet.equals(admin.publicKey)) {
+      expect(treasuryAfter - treasuryBefore).to.equal(BOND.toNumber());
+    }
+    expect(await provider.connection.getAccountInfo(bondPda)).to.equal(null);
+  });
+});
@@ -0,0 +1,215 @@
+import * as anchor from "@anchor-lang/core";
+import { Program } from "@anchor-lang/core";
+import { SwivPrivacy } from "../target/types/swiv_privacy";
+import { PublicKey, SystemProgram, Keypair, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { expect } from "chai";
+import { SEED_PROTOCOL, betOf as betOfPool, createPool, expectError, sleep } from "./utils";
+
+describe("Native SOL Pools", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = anchor.workspace.SwivPrivacy as Program<SwivPrivacy>;
+  const admin = (provider.wallet as anchor.Wallet).payer;
+
+  const endpoint = provider.connection.rpcEndpoint;
+  const isLocalnet = endpoint.includes("localhost") || endpoint.includes("127.0.0.1");
+
+  const [protocolPda] = PublicKey.findProgramAddressSync([SEED_PROTOCOL], program.programId);
+
+  const STAKE = LAMPORTS_PER_SOL / 2;
+  const SUBSIDY = LAMPORTS_PER_SOL / 10;
+  const OUTCOME = 75 * 1e6;
+
+  const alice = Keypair.generate();
+  const bob = Keypair.generate();
+
+  let poolPda: PublicKey;
+  let solVaultPda: PublicKey;
+  let treasuryWallet: PublicKey;
+  let protocolFeeBps: number;
+  let endTime: number;
+  let vaultReserve: number;
+
+  const betOf = (user: PublicKey) => betOfPool(program, poolPda, user);
+
+  const lamportsOf = (account: PublicKey) => provider.connection.getBalance(account);
+
+  const setProtocolFee = async (fee: number) => {
+    await program.methods
+      .updateConfig(null, new anchor.BN(fee), null, null, null, null, null, null, null, null)
+      .accountsPartial({ admin: admin.publicKey, roles: null, protocol: protocolPda })
+      .rpc();
+    await program.methods
+      .executeConfigChange()
+      .accountsPartial({ executor: admin.publicKey, protocol: protocolPda })
+      .rpc();
+  };
+
+  const nativeAccounts = {
+    poolVault: null,
+    userTokenAccount: null,
+    tokenMint: null,
+    tokenProgram: null,
+  };
+
+  before(async function () {
+    if (!isLocalnet) this.skip();
+
+    for (const kp of [alice, bob]) {
+      const sig = await provider.connection.requestAirdrop(kp.publicKey, 2 * LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(sig);
+    }
+
+    const protocol = await program.account.protocol.fetch(protocolPda);
+    protocolFeeBps = protocol.protocolFeeBps.toNumber();
+    treasuryWallet = protocol.treasuryWallet;
+
+    ({ pool: poolPda, vault: solVaultPda, end: endTime } = await createPool(program, {
+      title: "Native Pool",
+      native: true,
+      end: Math.floor(Date.now() / 1000) + 25,
+      subsidy: SUBSIDY,
+    }));
+
+    vaultReserve = (await lamportsOf(solVaultPda)) - SUBSIDY;
+  });
+
+  const initBet = (user: Keypair, referrer: PublicKey | null) =>
+    program.methods
+      .initBet(new anchor.BN(STAKE), "native_req", referrer)
+      .accountsPartial({
+        user: user.publicKey,
+        sponsor: admin.publicKey,
+        roles: null,
+        protocol: protocolPda,
+        pool: poolPda,
+        solVault: solVaultPda,
+        bet: betOf(user.publicKey),
+        ...nativeAccounts,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([user, admin])
+      .rpc();
+
+  it("holds the subsidy above the vault's rent-exempt reserve", async () => {
+    const pool = await program.account.pool.fetch(poolPda);
+    expect(pool.assetKind).to.deep.equal({ native: {} });
+    expect(pool.subsidyAmount.toNumber()).to.equal(SUBSIDY);
+    expect(pool.feeBps.toNumber()).to.equal(protocolFeeBps);
+    expect(vaultReserve).to.be.greaterThan(0);
+  });
+
+  it("rejects referrals", async () => {
+    await expectError(() => initBet(alice, bob.publicKey), "UnsupportedAssetKind");
+  });
+
+  it("stakes lamports into the SOL vault", async () => {
+    const before = await lamportsOf(alice.publicKey);
+    await initBet(alice, null);
+    await initBet(bob, null);
+
+    expect(before - (await lamportsOf(alice.publicKey))).to.equal(STAKE);
+    expect(await lamportsOf(solVaultPda)).to.equal(vaultReserve + SUBSIDY + 2 * STAKE);
+
+    const pool = await program.account.pool.fetch(poolPda);
+    expect(pool.totalStaked.toNumber()).to.equal(2 * STAKE);
+  });
+
+  it("keeps its fee snapshot when the protocol fee changes", async () => {
+    await setProtocolFee(protocolFeeBps + 100);
+    const pool = await program.account.pool.fetch(poolPda);
+    expect(pool.feeBps.toNumber()).to.equal(protocolFeeBps);
+  });
+
+  it("finalizes, pays the winner in lamports and closes the vault", async () => {
+    // Alice is exact; Bob misses the buffer and scores 0.
+    for (const [user, prediction] of [
+      [alice, OUTCOME],
+      [bob, OUTCOME + 10 * 1e6],
+    ] as [Keypair, number][]) {
+      await program.methods
+        .placeBet(new anchor.BN(prediction), null, "native_req")
+        .accountsPartial({
+          user: user.publicKey,
+          protocol: protocolPda,
+          pool: poolPda,
+          bet: betOf(user.publicKey),
+        })
+        .signers([user])
+        .rpc();
+    }
+
+    const waitTime = (endTime - Math.floor(Date.now() / 1000) + 2) * 1000;
+    if (waitTime > 0) await sleep(waitTime);
+
+    await program.methods
+      .resolvePool(new anchor.BN(OUTCOME))
+      .accountsPartial({ admin: admin.publicKey, protocol: protocolPda, pool: poolPda, roles: null })
+      .rpc();
+
+    await program.methods
+      .batchCalculateWeights()
+      .accountsPartial({ admin: admin.publicKey, pool: poolPda })
+      .remainingAccounts(
+        [alice, bob].map((kp) => ({
+          pubkey: betOf(kp.publicKey),
+          isSigner: false,
+          isWritable: true,
+        })),
+      )
+      .rpc();
+
+    const treasuryBefore = await lamportsOf(treasuryWallet);
+    await program.methods
+      .finalizeWeights()
+      .accountsPartial({
+        admin: admin.publicKey,
+        protocol: protocolPda,
+        pool: poolPda,
+        poolVault: null,
+        solVault: solVaultPda,
+        treasuryTokenAccount: null,
+        treasuryWallet,
+        creatorTokenAccount: null,
+        poolCreator: admin.publicKey,
+        tokenMint: null,
+        tokenProgram: null,
+      })
+      .rpc();
+
+    // Charged at the snapshot rate, not the raised protocol fee.
+    const protocolFee = Math.floor((2 * STAKE * protocolFeeBps) / 10000);
+    await setProtocolFee(protocolFeeBps);
+    if (!treasuryWallet.equals(admin.publicKey)) {
+      expect((await lamportsOf(treasuryWallet)) - treasuryBefore).to.equal(protocolFee);
+    }
+
+    const pool = await program.account.pool.fetch(poolPda);
+    const distributable = 2 * STAKE + SUBSIDY - protocolFee;
+    expect(pool.distributableAmount.toNumber()).to.equal(distributable);
+
+    const aliceBefore = await lamportsOf(alice.publicKey);
+    for (const user of [alice, bob]) {
+      await program.methods
+        .claimReward()
+        .accountsPartial({
+          user: user.publicKey,
+          sponsor: admin.publicKey,
+          poolCreator: admin.publicKey,
+          pool: poolPda,
+          solVault: solVaultPda,
+          bet: betOf(user.publicKey),
+          referralStats: null,
+          referralVault: null,
+          stakeReceipt: null,
+          ...nativeAccounts,
+        })
+        .signers([user, admin])
+        .rpc();
+    }
+
+    expect((await lamportsOf(alice.publicKey)) - aliceBefore).to.equal(distributable);
+    expect(await provider.connection.getAccountInfo(solVaultPda)).to.be.null;
+    expect(await provider.connection.getAccountInfo(poolPda)).to.be.null;
+  });
+});
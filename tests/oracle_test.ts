@@ -0,0 +1,115 @@
+import * as anchor from "@anchor-lang/core";
+import { Program } from "@anchor-lang/core";
+import { SwivPrivacy } from "../target/types/swiv_privacy";
+import { PublicKey, Keypair } from "@solana/web3.js";
+import { createMint } from "@solana/spl-token";
+import { expect } from "chai";
+import { createPool, expectError, sleep } from "./utils";
+
+// Loaded into the local validator from tests/fixtures/mock_pyth_price.json (see Anchor.toml).
+// Pyth v2 layout: price = 7_578_000_000, conf = 1_000_000, expo = -8, status = Trading,
+// published at 4_102_444_800 (2100-01-01).
+const MOCK_PYTH_PRICE = new PublicKey("E4ynkRHhB4pArtzfzbxyAzVWbzn482jHz2b6pcqvf3YP");
+const MOCK_PUBLISH_TIME = 4_102_444_800;
+
+describe("Oracle Resolution", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = anchor.workspace.SwivPrivacy as Program<SwivPrivacy>;
+  const admin = (provider.wallet as anchor.Wallet).payer;
+
+  const endpoint = provider.connection.rpcEndpoint;
+  const isLocalnet = endpoint.includes("localhost") || endpoint.includes("127.0.0.1");
+
+  let poolPda: PublicKey;
+  let latePricePool: PublicKey;
+  let endTime: number;
+
+  let mint: PublicKey;
+
+  // The mock price is published far in the future, so the default staleness accepts it.
+  const createOraclePool = (feed: PublicKey, priceFeed: PublicKey, maxStaleness = MOCK_PUBLISH_TIME) =>
+    createPool(program, {
+      title: "Oracle Pool",
+      mint,
+      end: Math.floor(Date.now() / 1000) + 15,
+      oracle: { feed, exponent: -6, maxStaleness: new anchor.BN(maxStaleness) },
+      priceFeed,
+    });
+
+  before(async function () {
+    if (!isLocalnet) this.skip();
+
+    mint = await createMint(provider.connection, admin, admin.publicKey, null, 6);
+
+    ({ pool: poolPda, end: endTime } = await createOraclePool(MOCK_PYTH_PRICE, MOCK_PYTH_PRICE));
+    ({ pool: latePricePool } = await createOraclePool(MOCK_PYTH_PRICE, MOCK_PYTH_PRICE, 60));
+  });
+
+  it("rejects a feed that is not owned by the Pyth program", async () => {
+    const fake = Keypair.generate().publicKey;
+    await expectError(() => createOraclePool(fake, admin.publicKey), "InvalidOracleAccount");
+  });
+
+  it("rejects a price account that differs from the configured feed", async () => {
+    const feed = Keypair.generate().publicKey;
+    await expectError(() => createOraclePool(feed, MOCK_PYTH_PRICE), "InvalidOracleAccount");
+  });
+
+  it("rejects resolution before end_time", async () => {
+    await expectError(
+      () =>
+        program.methods
+          .resolvePoolFromOracle()
+          .accountsPartial({ resolver: admin.publicKey, pool: poolPda, priceFeed: MOCK_PYTH_PRICE })
+          .rpc(),
+      "DurationTooShort",
+    );
+  });
+
+  it("rejects a price account that is not the pool feed", async () => {
+    const waitTime = (endTime - Math.floor(Date.now() / 1000) + 2) * 1000;
+    if (waitTime > 0) await sleep(waitTime);
+
+    await expectError(
+      () =>
+        program.methods
+          .resolvePoolFromOracle()
+          .accountsPartial({
+            resolver: admin.publicKey,
+            pool: poolPda,
+            priceFeed: Keypair.generate().publicKey,
+          })
+          .rpc(),
+      "InvalidOracleAccount",
+    );
+  });
+
+  it("rejects a price published more than max_staleness after end_time", async () => {
+    await expectError(
+      () =>
+        program.methods
+          .resolvePoolFromOracle()
+          .accountsPartial({ resolver: admin.publicKey, pool: latePricePool, priceFeed: MOCK_PYTH_PRICE })
+          .rpc(),
+      "OraclePriceStale",
+    );
+  });
+
+  it("resolves permissionlessly from the mock Pyth account", async () => {
+    const cranker = Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(cranker.publicKey, 1e9);
+    await provider.connection.confirmTransaction(sig);
+
+    await program.methods
+      .resolvePoolFromOracle()
+      .accountsPartial({ resolver: cranker.publicKey, pool: poolPda, priceFeed: MOCK_PYTH_PRICE })
+      .signers([cranker])
+      .rpc();
+
+    const pool = await program.account.pool.fetch(poolPda);
+    // 7_578_000_000 * 10^-8 rescaled to 10^-6 => 75_780_000
+    expect(pool.resolutionResult.toNumber()).to.equal(75_780_000);
+    expect(pool.status).to.deep.equal({ resolving: {} });
+  });
+});
@@ -21,6 +21,7 @@ import {
   SEED_BET,
   SEED_POOL,
   SEED_PROTOCOL,
+  SEED_STAKE_RECEIPT,
   PERMISSION_PROGRAM_ID,
   sleep,
   TEE_VALIDATOR,
@@ -96,12 +97,41 @@ describe("Production Flow", () => {
   let adminStartUsdc = 0;
   let userTotalStakes: number[] = [0, 0];
 
+  const stakeReceiptPdaOf = (bet: PublicKey) =>
+    PublicKey.findProgramAddressSync([SEED_STAKE_RECEIPT, bet.toBuffer()], program.programId)[0];
+
   const POOL_TITLE = `TEE-Pool-${Math.floor(Math.random() * 1000)}`;
   let END_TIME: anchor.BN;
   const PRICE_SCALE = 1_000_000;
   const toPriceBn = (value: number): anchor.BN =>
     new anchor.BN(Math.round(value * PRICE_SCALE));
   const TARGET_PRICE = toPriceBn(75.78);
+  const NO_CONVICTION = {
+    bonusBps: new anchor.BN(0),
+    decayBps: new anchor.BN(0),
+    floorBps: new anchor.BN(0),
+  };
+
+  // Unsigned prices with 6 decimals, hyperbolic error relative to the result.
+  const PRICE_OUTCOME = {
+    kind: { numeric: {} },
+    labels: [],
+    exponent: -6,
+    accuracyScale: new anchor.BN(0),
+  };
+
+  // Original fixed curve: 1 + 1.5 * t^2.
+  const DEFAULT_TIME_BONUS = {
+    curve: { quadratic: {} },
+    maxBonusBps: new anchor.BN(15000),
+    resetOnUpdate: false,
+  };
+
+  const NO_STAKE_LIMITS = {
+    minStake: new anchor.BN(0),
+    maxStakePerBet: new anchor.BN(0),
+    maxPoolTotal: new anchor.BN(0),
+  };
 
   const predictions = [toPriceBn(76.12), toPriceBn(75.11)];
   const updatedPredictions = [toPriceBn(74.76), toPriceBn(76.25)];
@@ -268,7 +298,7 @@ describe("Production Flow", () => {
 
     try {
       await trackBalanceChange("Initialize Protocol", true, () => program.methods
-        .initializeProtocol(new anchor.BN(300))
+        .initializeProtocol(new anchor.BN(300), new anchor.BN(0))
         .accountsPartial({
           admin: admin.publicKey,
           treasuryWallet: admin.publicKey,
@@ -279,14 +309,21 @@ describe("Production Flow", () => {
 
     // Set batch_settle_wait_duration to 0 so tests don't need to wait 60s between resolve and finalize
     await trackBalanceChange("Update Config", false, () => program.methods
-      .updateConfig(null, null, new anchor.BN(0))
+      .updateConfig(null, null, new anchor.BN(0), null, null, null, null, null, null, null)
       .accountsPartial({
         admin: admin.publicKey,
+        roles: null,
         protocol: protocolPda,
         systemProgram: SystemProgram.programId,
       })
       .rpc(), true);
 
+    // Config changes are timelocked; the localnet protocol is initialized with no delay.
+    await trackBalanceChange("Execute Config Change", false, () => program.methods
+      .executeConfigChange()
+      .accountsPartial({ executor: admin.publicKey, protocol: protocolPda })
+      .rpc(), true);
+
     const protocol = await fetchWithRetry<any>(program.account.protocol, protocolPda);
     poolId = protocol.totalPools.toNumber();
   });
@@ -324,14 +361,28 @@ describe("Production Flow", () => {
         START_TIME,
         END_TIME,
         toPriceBn(5),
-        new anchor.BN(3),
+        { bonusBps: new anchor.BN(3), decayBps: new anchor.BN(0), floorBps: new anchor.BN(0) },
+        null,
+        { hyperbolic: {} },
+        DEFAULT_TIME_BONUS,
+        PRICE_OUTCOME,
+        new anchor.BN(0),
+        new anchor.BN(0),
+        NO_STAKE_LIMITS,
+        new anchor.BN(0),
+        new anchor.BN(0),
+        { token: {} },
       )
       .accountsPartial({
         protocol: protocolPda,
         pool: poolPda,
         poolVault: vaultPda,
+        solVault: null,
         tokenMint: usdcMint,
+        resolverCommittee: null,
+        priceFeed: null,
         createdBy: admin.publicKey,
+        roles: null,
         createdByTokenAccount: adminAta.address,
         tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
@@ -360,14 +411,18 @@ describe("Production Flow", () => {
     const end = start.add(new anchor.BN(25));
     
     await trackBalanceChange("Create Pool (Cutoff Check)", true, () => program.methods
-      .createPool("Cutoff Check", start, end, toPriceBn(5), new anchor.BN(0))
+      .createPool("Cutoff Check", start, end, toPriceBn(5), NO_CONVICTION, null, { hyperbolic: {} }, DEFAULT_TIME_BONUS, PRICE_OUTCOME, new anchor.BN(0), new anchor.BN(0), NO_STAKE_LIMITS, new anchor.BN(0), new anchor.BN(0), { token: {} })
       .accountsPartial({
         protocol: protocolPda,
         pool: tempPoolPda,
         poolVault: tempVaultPda,
+        solVault: null,
         tokenMint: usdcMint,
+        resolverCommittee: null,
+        priceFeed: null,
         createdBy: admin.publicKey,
-        createdByTokenAccount: userAtas[0],
+        roles: null,
+        createdByTokenAccount: adminUsdcAta,
         tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
         rent: anchor.web3.SYSVAR_RENT_PUBKEY,
@@ -378,16 +433,19 @@ describe("Production Flow", () => {
 
     // Successful registration BEFORE cutoff
     await trackBalanceChange("Init Bet (Early Check)", true, () => program.methods
-      .initBet(new anchor.BN(10 * 1e6), "early_req")
+      .initBet(new anchor.BN(10 * 1e6), "early_req", null)
       .accountsPartial({
         user: users[0].publicKey,
         protocol: protocolPda,
         pool: tempPoolPda,
         poolVault: tempVaultPda,
+        solVault: null,
         userTokenAccount: userAtas[0],
         bet: tempBetPda,
+        tokenMint: usdcMint,
         tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
+        roles: null,
       })
       .signers([users[0], admin])
       .rpc(), true);
@@ -409,16 +467,19 @@ describe("Production Flow", () => {
     
     try {
       await program.methods
-        .initBet(new anchor.BN(10 * 1e6), "late_req")
+        .initBet(new anchor.BN(10 * 1e6), "late_req", null)
         .accountsPartial({
           user: lateUser.publicKey,
           protocol: protocolPda,
           pool: tempPoolPda,
           poolVault: tempVaultPda,
+          solVault: null,
           userTokenAccount: userAtas[1],
           bet: lateBetPda,
+          tokenMint: usdcMint,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
+          roles: null,
         })
         .signers([lateUser, admin])
         .rpc();
@@ -437,7 +498,7 @@ describe("Production Flow", () => {
 
     await trackBalanceChange("Resolve Pool (Cutoff Check)", false, () => program.methods
       .resolvePool(new anchor.BN(100_000))
-      .accountsPartial({ admin: admin.publicKey, protocol: protocolPda, pool: tempPoolPda })
+      .accountsPartial({ admin: admin.publicKey, protocol: protocolPda, pool: tempPoolPda, roles: null })
       .rpc(), true);
     
     const adminAta = (await getOrCreateAssociatedTokenAccount(provider.connection, admin, usdcMint, admin.publicKey)).address;
@@ -450,7 +511,12 @@ describe("Production Flow", () => {
         protocol: protocolPda,
         pool: tempPoolPda,
         poolVault: tempVaultPda,
+        solVault: null,
         treasuryTokenAccount: adminAta,
+        treasuryWallet: null,
+        creatorTokenAccount: adminAta,
+        poolCreator: null,
+        tokenMint: usdcMint,
         tokenProgram: TOKEN_PROGRAM_ID,
       })
       .rpc(), true);
@@ -467,10 +533,16 @@ describe("Production Flow", () => {
       .accountsPartial({
         user: users[0].publicKey,
         sponsor: admin.publicKey,
+        poolCreator: admin.publicKey,
         pool: tempPoolPda,
         poolVault: tempVaultPda,
+        solVault: null,
         bet: tempBetPda,
         userTokenAccount: userAtas[0],
+        referralStats: null,
+        referralVault: null,
+        stakeReceipt: null,
+        tokenMint: usdcMint,
         tokenProgram: TOKEN_PROGRAM_ID,
       })
       .signers([users[0], admin])
@@ -522,15 +594,18 @@ describe("Production Flow", () => {
 
       const tx = new anchor.web3.Transaction().add(
         await program.methods
-          .initBet(betAmount, requestId)
+          .initBet(betAmount, requestId, null)
           .accountsPartial({
             user: user.publicKey,
             sponsor: admin.publicKey,
+            roles: null,
             protocol: protocolPda,
             pool: poolPda,
             poolVault: vaultPda,
+            solVault: null,
             userTokenAccount: userAtas[i],
             bet: betPda,
+            tokenMint: usdcMint,
             tokenProgram: TOKEN_PROGRAM_ID,
             systemProgram: SystemProgram.programId,
           })
@@ -577,15 +652,18 @@ describe("Production Flow", () => {
     let failed = false;
     try {
       await program.methods
-        .initBet(duplicateAmount, duplicateRequestId)
+        .initBet(duplicateAmount, duplicateRequestId, null)
         .accountsPartial({
           user: user.publicKey,
           sponsor: admin.publicKey,
+          roles: null,
           protocol: protocolPda,
           pool: poolPda,
           poolVault: vaultPda,
+          solVault: null,
           userTokenAccount: userAtas[0],
           bet: betPdas[0],
+          tokenMint: usdcMint,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
         })
@@ -649,7 +727,7 @@ describe("Production Flow", () => {
         .instruction();
 
       const placeBetIx = await program.methods
-        .placeBet(predictions[i], requestId)
+        .placeBet(predictions[i], null, requestId)
         .accountsPartial({
           user: user.publicKey,
           pool: poolPda,
@@ -676,11 +754,12 @@ describe("Production Flow", () => {
         );
 
         const updateBetIx = await program.methods
-          .updateBet(updatedPredictions[i], new anchor.BN(0))
+          .updateBet(updatedPredictions[i], null)
           .accountsPartial({
             user: user.publicKey,
             pool: poolPda,
             bet: betPda,
+            stakeReceipt: null,
           })
           .instruction();
 
@@ -738,9 +817,14 @@ describe("Production Flow", () => {
       .accountsPartial({
         user: user.publicKey,
         pool: poolPda,
+        bet: betPda,
+        stakeReceipt: stakeReceiptPdaOf(betPda),
         poolVault: vaultPda,
+        solVault: null,
         userTokenAccount: userAtas[0],
+        tokenMint: usdcMint,
         tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
       })
       .signers([user])
       .rpc();
@@ -748,6 +832,13 @@ describe("Production Flow", () => {
     userTotalStakes[0] += additionalStake.toNumber() / 1e6;
     console.log(`      ✅ addStake executed on L1.`);
 
+    const receipt = await fetchWithRetry<any>(program.account.stakeReceipt, stakeReceiptPdaOf(betPda));
+    if (!receipt.totalAmount.eq(additionalStake) || receipt.nonce.toNumber() !== 1) {
+      throw new Error(
+        `❌ Stake receipt not recorded. Got amount ${receipt.totalAmount.toString()}, nonce ${receipt.nonce.toString()}`,
+      );
+    }
+
     // ── Step 2: Update prediction + record stake increase on TEE ─────────────
     console.log(
       `      🎯 Calling updateBet on TEE to record stake increase and update prediction...`,
@@ -771,11 +862,12 @@ describe("Production Flow", () => {
     const newPredictionForUpdate = updatedPredictions[0];
 
     const updateBetIx = await program.methods
-      .updateBet(newPredictionForUpdate, additionalStake)
+      .updateBet(newPredictionForUpdate, null)
       .accountsPartial({
         user: user.publicKey,
         pool: poolPda,
         bet: betPda,
+        stakeReceipt: stakeReceiptPdaOf(betPda),
       })
       .instruction();
 
@@ -942,7 +1034,9 @@ describe("Production Flow", () => {
         .delegatePool(new anchor.BN(poolId))
         .accountsPartial({
           admin: admin.publicKey,
+          roles: null,
           protocol: protocolPda,
+          poolCreator: admin.publicKey,
           bufferPool: bufferPool,
           delegationRecordPool: delegationRecordPool,
           delegationMetadataPool: delegationMetadataPool,
@@ -995,6 +1089,7 @@ describe("Production Flow", () => {
         .resolvePool(TARGET_PRICE)
         .accountsPartial({
           admin: admin.publicKey,
+          roles: null,
           protocol: protocolPda,
           pool: poolPda,
         })
@@ -1106,6 +1201,7 @@ describe("Production Flow", () => {
         .undelegatePool()
         .accountsPartial({
           admin: admin.publicKey,
+          roles: null,
           protocol: protocolPda,
           pool: poolPda,
         })
@@ -1197,7 +1293,12 @@ describe("Production Flow", () => {
           protocol: protocolPda,
           pool: poolPda,
           poolVault: vaultPda,
+          solVault: null,
           treasuryTokenAccount: adminAta.address,
+          treasuryWallet: null,
+          creatorTokenAccount: adminAta.address,
+          poolCreator: null,
+          tokenMint: usdcMint,
           tokenProgram: TOKEN_PROGRAM_ID,
         })
         .signers([admin])
@@ -1234,10 +1335,16 @@ describe("Production Flow", () => {
           .accountsPartial({
             user: user.publicKey,
             sponsor: admin.publicKey,
+            poolCreator: admin.publicKey,
             pool: poolPda,
             poolVault: vaultPda,
+            solVault: null,
             bet: userBetPda,
             userTokenAccount: userAta,
+            referralStats: null,
+            referralVault: null,
+            stakeReceipt: i === 0 ? stakeReceiptPdaOf(userBetPda) : null,
+            tokenMint: usdcMint,
             tokenProgram: TOKEN_PROGRAM_ID,
           })
           .signers([user, admin])
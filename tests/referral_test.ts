@@ -0,0 +1,329 @@
+import * as anchor from "@anchor-lang/core";
+import { Program } from "@anchor-lang/core";
+import { SwivPrivacy } from "../target/types/swiv_privacy";
+import { PublicKey, SystemProgram, Keypair } from "@solana/web3.js";
+import { createMint, mintTo, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  SEED_PROTOCOL,
+  SEED_REFERRAL,
+  SEED_REFERRAL_VAULT,
+  ataOf as ataOfMint,
+  balanceOf,
+  betOf as betOfPool,
+  createPool,
+  expectError,
+  initBet as initPoolBet,
+  sleep,
+} from "./utils";
+
+describe("Referrals", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = anchor.workspace.SwivPrivacy as Program<SwivPrivacy>;
+  const admin = (provider.wallet as anchor.Wallet).payer;
+
+  const endpoint = provider.connection.rpcEndpoint;
+  const isLocalnet = endpoint.includes("localhost") || endpoint.includes("127.0.0.1");
+
+  const [protocolPda] = PublicKey.findProgramAddressSync([SEED_PROTOCOL], program.programId);
+
+  const STAKE = 10 * 1e6;
+  const OUTCOME = 75 * 1e6;
+  const REFERRAL_FEE_BPS = 5000;
+
+  const referrer = Keypair.generate();
+  const referred = Keypair.generate();
+  const organic = Keypair.generate();
+
+  let mint: PublicKey;
+  let adminAta: PublicKey;
+  let poolPda: PublicKey;
+  let vaultPda: PublicKey;
+  let statsPda: PublicKey;
+  let referralVaultPda: PublicKey;
+  let endTime: number;
+  let protocolFeeBps: number;
+
+  const ataOf = (owner: PublicKey) => ataOfMint(program, mint, owner);
+
+  const betOf = (user: PublicKey, pool = poolPda) => betOfPool(program, pool, user);
+
+  const initBet = (user: Keypair, referrerKey: PublicKey | null, pool = poolPda) =>
+    initPoolBet(program, {
+      pool,
+      mint,
+      user,
+      amount: STAKE,
+      requestId: "referral_req",
+      referrer: referrerKey,
+    });
+
+  const createReferralPool = (title: string) =>
+    createPool(program, { title, mint, end: Math.floor(Date.now() / 1000) + 25 });
+
+  before(async function () {
+    if (!isLocalnet) this.skip();
+
+    for (const kp of [referrer, referred, organic]) {
+      const sig = await provider.connection.requestAirdrop(kp.publicKey, 1e9);
+      await provider.connection.confirmTransaction(sig);
+    }
+
+    const protocol = await program.account.protocol.fetch(protocolPda);
+    protocolFeeBps = protocol.protocolFeeBps.toNumber();
+
+    await program.methods
+      .updateConfig(null, null, new anchor.BN(0), null, null, null, null, new anchor.BN(REFERRAL_FEE_BPS), null, null)
+      .accountsPartial({ admin: admin.publicKey, protocol: protocolPda, roles: null })
+      .rpc();
+    await program.methods
+      .executeConfigChange()
+      .accountsPartial({ executor: admin.publicKey, protocol: protocolPda })
+      .rpc();
+
+    mint = await createMint(provider.connection, admin, admin.publicKey, null, 6);
+    adminAta = await ataOf(admin.publicKey);
+    for (const kp of [referred, organic]) {
+      await mintTo(provider.connection, admin, mint, await ataOf(kp.publicKey), admin, 2 * STAKE);
+    }
+
+    [statsPda] = PublicKey.findProgramAddressSync(
+      [SEED_REFERRAL, referrer.publicKey.toBuffer(), mint.toBuffer()],
+      program.programId,
+    );
+    [referralVaultPda] = PublicKey.findProgramAddressSync(
+      [SEED_REFERRAL_VAULT, statsPda.toBuffer()],
+      program.programId,
+    );
+
+    ({ pool: poolPda, vault: vaultPda, end: endTime } = await createReferralPool("Referral Pool"));
+
+    await program.methods
+      .initReferralStats()
+      .accountsPartial({
+        payer: admin.publicKey,
+        referrer: referrer.publicKey,
+        mint,
+        referralStats: statsPda,
+        referralVault: referralVaultPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+  });
+
+  after(async function () {
+    if (!isLocalnet) return;
+    await program.methods
+      .updateConfig(null, null, null, null, null, null, null, new anchor.BN(0), null, null)
+      .accountsPartial({ admin: admin.publicKey, protocol: protocolPda, roles: null })
+      .rpc();
+  });
+
+  it("rejects self-referral", async () => {
+    await expectError(() => initBet(referred, referred.publicKey), "InvalidReferrer");
+  });
+
+  it("records the referrer on the bet and the referred stake on the pool", async () => {
+    await initBet(referred, referrer.publicKey);
+    await initBet(organic, null);
+
+    const bet = await program.account.bet.fetch(betOf(referred.publicKey));
+    expect(bet.referrer.toBase58()).to.equal(referrer.publicKey.toBase58());
+    expect(bet.referredAmount.toNumber()).to.equal(STAKE);
+
+    const pool = await program.account.pool.fetch(poolPda);
+    expect(pool.referredStake.toNumber()).to.equal(STAKE);
+  });
+
+  it("routes the referral share of the protocol fee to the referrer", async () => {
+    // The referred bet is exact; the organic bet misses the buffer and scores 0.
+    for (const [user, prediction] of [
+      [referred, OUTCOME],
+      [organic, OUTCOME + 10 * 1e6],
+    ] as [Keypair, number][]) {
+      await program.methods
+        .placeBet(new anchor.BN(prediction), null, "referral_req")
+        .accountsPartial({
+          user: user.publicKey,
+          protocol: protocolPda,
+          pool: poolPda,
+          bet: betOf(user.publicKey),
+        })
+        .signers([user])
+        .rpc();
+    }
+
+    const waitTime = (endTime - Math.floor(Date.now() / 1000) + 2) * 1000;
+    if (waitTime > 0) await sleep(waitTime);
+
+    await program.methods
+      .resolvePool(new anchor.BN(OUTCOME))
+      .accountsPartial({ admin: admin.publicKey, protocol: protocolPda, pool: poolPda, roles: null })
+      .rpc();
+
+    await program.methods
+      .batchCalculateWeights()
+      .accountsPartial({ admin: admin.publicKey, pool: poolPda })
+      .remainingAccounts(
+        [referred, organic].map((kp) => ({
+          pubkey: betOf(kp.publicKey),
+          isSigner: false,
+          isWritable: true,
+        })),
+      )
+      .rpc();
+
+    await program.methods
+      .finalizeWeights()
+      .accountsPartial({
+        admin: admin.publicKey,
+        protocol: protocolPda,
+        pool: poolPda,
+        poolVault: vaultPda,
+        solVault: null,
+        treasuryTokenAccount: adminAta,
+        treasuryWallet: null,
+        creatorTokenAccount: adminAta,
+        poolCreator: null,
+        tokenMint: mint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    const protocolFee = Math.floor((2 * STAKE * protocolFeeBps) / 10000);
+    const expectedShare = Math.floor(Math.floor((protocolFee * REFERRAL_FEE_BPS) / 10000) / 2);
+
+    const pool = await program.account.pool.fetch(poolPda);
+    expect(pool.referralReserve.toNumber()).to.equal(expectedShare);
+
+    for (const user of [referred, organic]) {
+      const isReferred = user === referred;
+      await program.methods
+        .claimReward()
+        .accountsPartial({
+          user: user.publicKey,
+          sponsor: admin.publicKey,
+          poolCreator: admin.publicKey,
+          pool: poolPda,
+          poolVault: vaultPda,
+          solVault: null,
+          bet: betOf(user.publicKey),
+          userTokenAccount: await ataOf(user.publicKey),
+          referralStats: isReferred ? statsPda : null,
+          referralVault: isReferred ? referralVaultPda : null,
+          stakeReceipt: null,
+          tokenMint: mint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user, admin])
+        .rpc();
+    }
+
+    const stats = await program.account.referralStats.fetch(statsPda);
+    expect(stats.totalEarned.toNumber()).to.equal(expectedShare);
+
+    const referrerAta = await ataOf(referrer.publicKey);
+    await program.methods
+      .claimReferralFees()
+      .accountsPartial({
+        referrer: referrer.publicKey,
+        referralStats: statsPda,
+        referralVault: referralVaultPda,
+        referrerTokenAccount: referrerAta,
+        tokenMint: mint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([referrer])
+      .rpc();
+
+    expect(await balanceOf(program, referrerAta)).to.equal(expectedShare);
+  });
+
+  it("pays the referral share when the referred bet is the last claimant", async () => {
+    const { pool, vault, end } = await createReferralPool("Referral Last Claimant Pool");
+    await initBet(referred, referrer.publicKey, pool);
+
+    await program.methods
+      .placeBet(new anchor.BN(OUTCOME), null, "referral_req")
+      .accountsPartial({
+        user: referred.publicKey,
+        protocol: protocolPda,
+        pool,
+        bet: betOf(referred.publicKey, pool),
+      })
+      .signers([referred])
+      .rpc();
+
+    const waitTime = (end - Math.floor(Date.now() / 1000) + 2) * 1000;
+    if (waitTime > 0) await sleep(waitTime);
+
+    await program.methods
+      .resolvePool(new anchor.BN(OUTCOME))
+      .accountsPartial({ admin: admin.publicKey, protocol: protocolPda, pool, roles: null })
+      .rpc();
+
+    await program.methods
+      .batchCalculateWeights()
+      .accountsPartial({ admin: admin.publicKey, pool })
+      .remainingAccounts([
+        { pubkey: betOf(referred.publicKey, pool), isSigner: false, isWritable: true },
+      ])
+      .rpc();
+
+    await program.methods
+      .finalizeWeights()
+      .accountsPartial({
+        admin: admin.publicKey,
+        protocol: protocolPda,
+        pool,
+        poolVault: vault,
+        solVault: null,
+        treasuryTokenAccount: adminAta,
+        treasuryWallet: null,
+        creatorTokenAccount: adminAta,
+        poolCreator: null,
+        tokenMint: mint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    const { referralReserve } = await program.account.pool.fetch(pool);
+    const reserve = referralReserve.toNumber();
+    expect(reserve).to.be.greaterThan(0);
+
+    const vaultBalance = await balanceOf(program, vault);
+    const userAta = await ataOf(referred.publicKey);
+    const userBefore = await balanceOf(program, userAta);
+    const earnedBefore = (await program.account.referralStats.fetch(statsPda)).totalEarned.toNumber();
+
+    await program.methods
+      .claimReward()
+      .accountsPartial({
+        user: referred.publicKey,
+        sponsor: admin.publicKey,
+        poolCreator: admin.publicKey,
+        pool,
+        poolVault: vault,
+        solVault: null,
+        bet: betOf(referred.publicKey, pool),
+        userTokenAccount: userAta,
+        referralStats: statsPda,
+        referralVault: referralVaultPda,
+        stakeReceipt: null,
+        tokenMint: mint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([referred, admin])
+      .rpc();
+
+    const userAfter = await balanceOf(program, userAta);
+    expect(userAfter - userBefore).to.equal(vaultBalance - reserve);
+
+    const stats = await program.account.referralStats.fetch(statsPda);
+    expect(stats.totalEarned.toNumber() - earnedBefore).to.equal(reserve);
+
+    expect(await provider.connection.getAccountInfo(pool)).to.be.null;
+  });
+});
@@ -0,0 +1,317 @@
+import * as anchor from "@anchor-lang/core";
+import { Program } from "@anchor-lang/core";
+import { SwivPrivacy } from "../target/types/swiv_privacy";
+import { PublicKey, SystemProgram, Keypair } from "@solana/web3.js";
+import { TOKEN_2022_PROGRAM_ID, mintTo } from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  SEED_PROTOCOL,
+  ataOf as ataOfMint,
+  balanceOf as balanceOfAccount,
+  createPool,
+  createTransferFeeMint,
+  expectError,
+  sleep,
+  withheldFeesOf,
+} from "./utils";
+
+const SEED_PROPOSAL = Buffer.from("proposal");
+const SEED_BOND_VAULT = Buffer.from("bond_vault");
+
+describe("Optimistic Resolution", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = anchor.workspace.SwivPrivacy as Program<SwivPrivacy>;
+  const admin = (provider.wallet as anchor.Wallet).payer;
+
+  const endpoint = provider.connection.rpcEndpoint;
+  const isLocalnet = endpoint.includes("localhost") || endpoint.includes("127.0.0.1");
+
+  const [protocolPda] = PublicKey.findProgramAddressSync([SEED_PROTOCOL], program.programId);
+
+  const BOND = 1_000_000;
+  const TRANSFER_FEE_BPS = 100;
+  const DISPUTE_WINDOW = 3;
+  const proposer = Keypair.generate();
+  const disputer = Keypair.generate();
+
+  let mint: PublicKey;
+  let defaultWindow: anchor.BN;
+
+  const afterFee = (amount: number) => amount - Math.ceil((amount * TRANSFER_FEE_BPS) / 10000);
+
+  const ataOf = (owner: PublicKey) => ataOfMint(program, mint, owner, TOKEN_2022_PROGRAM_ID);
+
+  const balanceOf = (account: PublicKey) => balanceOfAccount(program, account);
+
+  const pdasOf = (pool: PublicKey) => ({
+    proposal: PublicKey.findProgramAddressSync([SEED_PROPOSAL, pool.toBuffer()], program.programId)[0],
+    bondVault: PublicKey.findProgramAddressSync([SEED_BOND_VAULT, pool.toBuffer()], program.programId)[0],
+  });
+
+  const createEndedPool = async () => {
+    const { pool } = await createPool(program, {
+      title: "Optimistic Pool",
+      mint,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+      end: Math.floor(Date.now() / 1000) + 2,
+    });
+
+    await sleep(3000);
+    return pool;
+  };
+
+  const propose = async (pool: PublicKey, outcome: number) => {
+    const { proposal, bondVault } = pdasOf(pool);
+    await program.methods
+      .proposeOutcome(new anchor.BN(outcome))
+      .accountsPartial({
+        proposer: proposer.publicKey,
+        protocol: protocolPda,
+        pool,
+        proposal,
+        bondVault,
+        tokenMint: mint,
+        proposerTokenAccount: await ataOf(proposer.publicKey),
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([proposer])
+      .rpc();
+  };
+
+  before(async function () {
+    if (!isLocalnet) this.skip();
+
+    for (const kp of [proposer, disputer]) {
+      const sig = await provider.connection.requestAirdrop(kp.publicKey, 1e9);
+      await provider.connection.confirmTransaction(sig);
+    }
+
+    // 1% transfer fee so every bond movement loses part of its amount in transit.
+    mint = await createTransferFeeMint(program, TRANSFER_FEE_BPS, BOND);
+
+    for (const kp of [proposer, disputer]) {
+      await mintTo(
+        provider.connection,
+        admin,
+        mint,
+        await ataOf(kp.publicKey),
+        admin,
+        10 * BOND,
+        [],
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
+      );
+    }
+
+    defaultWindow = (await program.account.protocol.fetch(protocolPda)).disputeWindow;
+    await program.methods
+      .updateConfig(
+        null,
+        null,
+        null,
+        new anchor.BN(DISPUTE_WINDOW),
+        new anchor.BN(BOND),
+        admin.publicKey,
+        null,
+        null,
+        null,
+        null,
+      )
+      .accountsPartial({ admin: admin.publicKey, protocol: protocolPda, roles: null })
+      .rpc();
+  });
+
+  after(async () => {
+    if (!isLocalnet) return;
+    await program.methods
+      .updateConfig(null, null, null, defaultWindow, new anchor.BN(0), null, null, null, null, null)
+      .accountsPartial({ admin: admin.publicKey, protocol: protocolPda, roles: null })
+      .rpc();
+  });
+
+  it("settles an undisputed proposal and returns the net bond", async () => {
+    const pool = await createEndedPool();
+    const { proposal, bondVault } = pdasOf(pool);
+    const proposerAta = await ataOf(proposer.publicKey);
+
+    await propose(pool, 42);
+
+    const escrowed = afterFee(BOND);
+    const recorded = await program.account.resolutionProposal.fetch(proposal);
+    expect(recorded.bondAmount.toNumber()).to.equal(escrowed);
+    expect(await balanceOf(bondVault)).to.equal(escrowed);
+
+    await expectError(
+      () =>
+        program.methods
+          .settleProposal()
+          .accountsPartial({
+            payer: admin.publicKey,
+            pool,
+            proposal,
+            proposer: proposer.publicKey,
+            bondVault,
+            proposerTokenAccount: proposerAta,
+            tokenMint: mint,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+          })
+          .rpc(),
+      "DisputeWindowOpen",
+    );
+
+    await sleep((DISPUTE_WINDOW + 1) * 1000);
+
+    const before = await balanceOf(proposerAta);
+    const withheldBefore = await withheldFeesOf(program, mint);
+    await program.methods
+      .settleProposal()
+      .accountsPartial({
+        payer: admin.publicKey,
+        pool,
+        proposal,
+        proposer: proposer.publicKey,
+        bondVault,
+        proposerTokenAccount: proposerAta,
+        tokenMint: mint,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .rpc();
+
+    expect(await balanceOf(proposerAta)).to.equal(before + afterFee(escrowed));
+    // The fee withheld on the bond's way in is harvested to the mint so the vault can close.
+    expect(await withheldFeesOf(program, mint)).to.equal(withheldBefore + BOND - escrowed);
+    expect(await provider.connection.getAccountInfo(bondVault)).to.be.null;
+    expect(await provider.connection.getAccountInfo(proposal)).to.be.null;
+
+    const state = await program.account.pool.fetch(pool);
+    expect(state.status).to.have.property("resolving");
+    expect(state.resolutionResult.toNumber()).to.equal(42);
+  });
+
+  it("arbitrates a dispute, refunding the winner and slashing the loser's net bond", async () => {
+    const pool = await createEndedPool();
+    const { proposal, bondVault } = pdasOf(pool);
+    const proposerAta = await ataOf(proposer.publicKey);
+    const disputerAta = await ataOf(disputer.publicKey);
+    const { treasuryWallet } = await program.account.protocol.fetch(protocolPda);
+    const treasuryAta = await ataOf(treasuryWallet);
+
+    await propose(pool, 42);
+
+    await program.methods
+      .disputeOutcome()
+      .accountsPartial({
+        disputer: disputer.publicKey,
+        pool,
+        proposal,
+        bondVault,
+        disputerTokenAccount: disputerAta,
+        tokenMint: mint,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .signers([disputer])
+      .rpc();
+
+    const proposerBond = afterFee(BOND);
+    const disputerBond = afterFee(proposerBond);
+    expect(await balanceOf(bondVault)).to.equal(proposerBond + disputerBond);
+    expect((await program.account.pool.fetch(pool)).status).to.have.property("disputed");
+
+    const disputerBefore = await balanceOf(disputerAta);
+    const treasuryBefore = await balanceOf(treasuryAta);
+    const withheldBefore = await withheldFeesOf(program, mint);
+
+    // The disputer was right: the proposer's bond goes to the treasury.
+    await program.methods
+      .arbitrateDispute(new anchor.BN(7))
+      .accountsPartial({
+        arbiter: admin.publicKey,
+        protocol: protocolPda,
+        pool,
+        proposal,
+        proposer: proposer.publicKey,
+        bondVault,
+        proposerTokenAccount: proposerAta,
+        disputerTokenAccount: disputerAta,
+        treasuryTokenAccount: treasuryAta,
+        tokenMint: mint,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .rpc();
+
+    expect(await balanceOf(disputerAta)).to.equal(disputerBefore + afterFee(disputerBond));
+    expect(await balanceOf(treasuryAta)).to.equal(treasuryBefore + afterFee(proposerBond));
+    expect(await withheldFeesOf(program, mint)).to.equal(withheldBefore + BOND - disputerBond);
+    expect(await provider.connection.getAccountInfo(bondVault)).to.be.null;
+
+    const state = await program.account.pool.fetch(pool);
+    expect(state.status).to.have.property("resolving");
+    expect(state.resolutionResult.toNumber()).to.equal(7);
+  });
+
+  it("rejects proposals while the resolution bond is zero", async () => {
+    const pool = await createEndedPool();
+    const setBond = (bond: number) =>
+      program.methods
+        .updateConfig(null, null, null, null, new anchor.BN(bond), null, null, null, null, null)
+        .accountsPartial({ admin: admin.publicKey, protocol: protocolPda, roles: null })
+        .rpc();
+
+    await setBond(0);
+    try {
+      await expectError(() => propose(pool, 42), "ResolutionBondNotSet");
+    } finally {
+      await setBond(BOND);
+    }
+  });
+
+  it("only voids a disputed pool once the arbiter has timed out", async () => {
+    const pool = await createEndedPool();
+    const { proposal, bondVault } = pdasOf(pool);
+    const proposerAta = await ataOf(proposer.publicKey);
+    const disputerAta = await ataOf(disputer.publicKey);
+
+    await propose(pool, 42);
+    await program.methods
+      .disputeOutcome()
+      .accountsPartial({
+        disputer: disputer.publicKey,
+        pool,
+        proposal,
+        bondVault,
+        disputerTokenAccount: disputerAta,
+        tokenMint: mint,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .signers([disputer])
+      .rpc();
+
+    const voidPool = (withBonds: boolean) =>
+      program.methods
+        .voidPool({ dataUnavailable: {} })
+        .accountsPartial({
+          admin: admin.publicKey,
+          protocol: protocolPda,
+          pool,
+          poolVault: null,
+          solVault: null,
+          creatorTokenAccount: null,
+          poolCreator: null,
+          tokenMint: mint,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          proposal: withBonds ? proposal : null,
+          bondVault: withBonds ? bondVault : null,
+          proposer: withBonds ? proposer.publicKey : null,
+          proposerTokenAccount: withBonds ? proposerAta : null,
+          disputerTokenAccount: withBonds ? disputerAta : null,
+        })
+        .rpc();
+
+    await expectError(() => voidPool(false), "DisputeAccountsMissing");
+    // DISPUTE_TIMEOUT is 7 days, so the arbiter is still within its window here.
+    await expectError(() => voidPool(true), "DisputeTimeoutNotReached");
+    expect((await program.account.pool.fetch(pool)).status).to.have.property("disputed");
+  });
+});
@@ -0,0 +1,214 @@
+import * as anchor from "@anchor-lang/core";
+import { Program } from "@anchor-lang/core";
+import { SwivPrivacy } from "../target/types/swiv_privacy";
+import { PublicKey, SystemProgram, Keypair } from "@solana/web3.js";
+import { createMint, mintTo, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  SEED_PROTOCOL,
+  SEED_ROLES,
+  ataOf as ataOfMint,
+  betOf as betOfPool,
+  createPool,
+  expectError,
+} from "./utils";
+
+const ROLE_RESOLVER = 1 << 1;
+const ROLE_PAUSER = 1 << 2;
+const ROLE_FEE_MANAGER = 1 << 3;
+const ROLE_SPONSOR = 1 << 4;
+
+describe("Roles", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = anchor.workspace.SwivPrivacy as Program<SwivPrivacy>;
+  const admin = (provider.wallet as anchor.Wallet).payer;
+
+  const endpoint = provider.connection.rpcEndpoint;
+  const isLocalnet = endpoint.includes("localhost") || endpoint.includes("127.0.0.1");
+
+  const [protocolPda] = PublicKey.findProgramAddressSync([SEED_PROTOCOL], program.programId);
+
+  const STAKE = 10 * 1e6;
+
+  const bot = Keypair.generate();
+  const alice = Keypair.generate();
+  const bob = Keypair.generate();
+  const feeManager = Keypair.generate();
+
+  const [botRolesPda] = PublicKey.findProgramAddressSync(
+    [SEED_ROLES, bot.publicKey.toBuffer()],
+    program.programId,
+  );
+
+  const [feeManagerRolesPda] = PublicKey.findProgramAddressSync(
+    [SEED_ROLES, feeManager.publicKey.toBuffer()],
+    program.programId,
+  );
+
+  let mint: PublicKey;
+  let poolPda: PublicKey;
+  let vaultPda: PublicKey;
+
+  const ataOf = (owner: PublicKey) => ataOfMint(program, mint, owner);
+
+  const betOf = (user: PublicKey) => betOfPool(program, poolPda, user);
+
+  const sponsoredInitBet = async (user: Keypair) =>
+    program.methods
+      .initBet(new anchor.BN(STAKE), "roles_req", null)
+      .accountsPartial({
+        user: user.publicKey,
+        sponsor: bot.publicKey,
+        roles: botRolesPda,
+        protocol: protocolPda,
+        pool: poolPda,
+        poolVault: vaultPda,
+        solVault: null,
+        userTokenAccount: await ataOf(user.publicKey),
+        bet: betOf(user.publicKey),
+        tokenMint: mint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([user, bot])
+      .rpc();
+
+  before(async function () {
+    if (!isLocalnet) this.skip();
+
+    for (const kp of [bot, alice, bob, feeManager]) {
+      const sig = await provider.connection.requestAirdrop(kp.publicKey, 1e9);
+      await provider.connection.confirmTransaction(sig);
+    }
+
+    mint = await createMint(provider.connection, admin, admin.publicKey, null, 6);
+    for (const kp of [alice, bob]) {
+      await mintTo(provider.connection, admin, mint, await ataOf(kp.publicKey), admin, STAKE);
+    }
+
+    ({ pool: poolPda, vault: vaultPda } = await createPool(program, { title: "Roles Pool", mint }));
+  });
+
+  it("rejects an empty or unknown role set", async () => {
+    for (const bits of [0, 1 << 7]) {
+      await expectError(
+        () =>
+          program.methods
+            .grantRole(bot.publicKey, bits)
+            .accountsPartial({ admin: admin.publicKey, protocol: protocolPda, roles: botRolesPda })
+            .rpc(),
+        "InvalidRoles",
+      );
+    }
+  });
+
+  it("lets a sponsor pay bet rent without any other power", async () => {
+    await program.methods
+      .grantRole(bot.publicKey, ROLE_SPONSOR)
+      .accountsPartial({ admin: admin.publicKey, protocol: protocolPda, roles: botRolesPda })
+      .rpc();
+
+    const roles = await program.account.roles.fetch(botRolesPda);
+    expect(roles.holder.toBase58()).to.equal(bot.publicKey.toBase58());
+    expect(roles.roles).to.equal(ROLE_SPONSOR);
+
+    await sponsoredInitBet(alice);
+    const bet = await program.account.bet.fetch(betOf(alice.publicKey));
+    expect(bet.sponsor.toBase58()).to.equal(bot.publicKey.toBase58());
+
+    await expectError(
+      () =>
+        program.methods
+          .resolvePool(new anchor.BN(0))
+          .accountsPartial({ admin: bot.publicKey, roles: botRolesPda, protocol: protocolPda, pool: poolPda })
+          .signers([bot])
+          .rpc(),
+      "Unauthorized",
+    );
+    await expectError(
+      () =>
+        program.methods
+          .setPause(true)
+          .accountsPartial({ admin: bot.publicKey, roles: botRolesPda, protocol: protocolPda })
+          .signers([bot])
+          .rpc(),
+      "Unauthorized",
+    );
+  });
+
+  it("grants further roles additively", async () => {
+    await program.methods
+      .grantRole(bot.publicKey, ROLE_RESOLVER | ROLE_PAUSER)
+      .accountsPartial({ admin: admin.publicKey, protocol: protocolPda, roles: botRolesPda })
+      .rpc();
+
+    const roles = await program.account.roles.fetch(botRolesPda);
+    expect(roles.roles).to.equal(ROLE_SPONSOR | ROLE_RESOLVER | ROLE_PAUSER);
+  });
+
+  it("revokes roles and closes the account once none remain", async () => {
+    await program.methods
+      .revokeRole(ROLE_RESOLVER | ROLE_PAUSER)
+      .accountsPartial({ admin: admin.publicKey, protocol: protocolPda, roles: botRolesPda })
+      .rpc();
+    expect((await program.account.roles.fetch(botRolesPda)).roles).to.equal(ROLE_SPONSOR);
+
+    await program.methods
+      .revokeRole(ROLE_SPONSOR)
+      .accountsPartial({ admin: admin.publicKey, protocol: protocolPda, roles: botRolesPda })
+      .rpc();
+    expect(await provider.connection.getAccountInfo(botRolesPda)).to.be.null;
+
+    const bobAta = await ataOf(bob.publicKey);
+    await expectError(
+      () =>
+        program.methods
+          .initBet(new anchor.BN(STAKE), "roles_req", null)
+          .accountsPartial({
+            user: bob.publicKey,
+            sponsor: bot.publicKey,
+            roles: null,
+            protocol: protocolPda,
+            pool: poolPda,
+            poolVault: vaultPda,
+            solVault: null,
+            userTokenAccount: bobAta,
+            bet: betOf(bob.publicKey),
+            tokenMint: mint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([bob, bot])
+          .rpc(),
+      "Unauthorized",
+    );
+  });
+
+  it("keeps the dispute window, resolution bond and minimum claim window away from fee managers", async () => {
+    await program.methods
+      .grantRole(feeManager.publicKey, ROLE_FEE_MANAGER)
+      .accountsPartial({ admin: admin.publicKey, protocol: protocolPda, roles: feeManagerRolesPda })
+      .rpc();
+
+    const updateConfig = (
+      disputeWindow: anchor.BN | null,
+      resolutionBond: anchor.BN | null,
+      referralFeeBps: anchor.BN | null,
+      minClaimWindow: anchor.BN | null = null,
+    ) =>
+      program.methods
+        .updateConfig(null, null, null, disputeWindow, resolutionBond, null, null, referralFeeBps, null, minClaimWindow)
+        .accountsPartial({ admin: feeManager.publicKey, protocol: protocolPda, roles: feeManagerRolesPda })
+        .signers([feeManager])
+        .rpc();
+
+    await expectError(() => updateConfig(new anchor.BN(1), null, null), "Unauthorized");
+    await expectError(() => updateConfig(null, new anchor.BN(0), null), "Unauthorized");
+    await expectError(() => updateConfig(null, null, null, new anchor.BN(0)), "Unauthorized");
+
+    // Economic knobs remain open to the role.
+    const { referralFeeBps } = await program.account.protocol.fetch(protocolPda);
+    await updateConfig(null, null, referralFeeBps);
+  });
+});
@@ -0,0 +1,72 @@
+import * as anchor from "@anchor-lang/core";
+import { Program } from "@anchor-lang/core";
+import { SwivPrivacy } from "../target/types/swiv_privacy";
+import { PublicKey, Keypair } from "@solana/web3.js";
+import { createMint, mintTo } from "@solana/spl-token";
+import { expect } from "chai";
+import { ataOf, createPool, expectError, initBet } from "./utils";
+
+describe("Stake Limits", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = anchor.workspace.SwivPrivacy as Program<SwivPrivacy>;
+  const admin = (provider.wallet as anchor.Wallet).payer;
+
+  const endpoint = provider.connection.rpcEndpoint;
+  const isLocalnet = endpoint.includes("localhost") || endpoint.includes("127.0.0.1");
+
+  const MIN_STAKE = 5 * 1e6;
+  const MAX_PER_BET = 20 * 1e6;
+  const MAX_TOTAL = 30 * 1e6;
+
+  const alice = Keypair.generate();
+  const bob = Keypair.generate();
+
+  let mint: PublicKey;
+  let poolPda: PublicKey;
+
+  const createCappedPool = (minStake: number, maxStakePerBet: number, maxPoolTotal: number) =>
+    createPool(program, { title: "Capped Pool", mint, minStake, maxStakePerBet, maxPoolTotal });
+
+  const bet = (user: Keypair, amount: number) =>
+    initBet(program, { pool: poolPda, mint, user, amount, requestId: "limits_req" });
+
+  before(async function () {
+    if (!isLocalnet) this.skip();
+
+    for (const kp of [alice, bob]) {
+      const sig = await provider.connection.requestAirdrop(kp.publicKey, 1e9);
+      await provider.connection.confirmTransaction(sig);
+    }
+
+    mint = await createMint(provider.connection, admin, admin.publicKey, null, 6);
+    for (const kp of [alice, bob]) {
+      await mintTo(provider.connection, admin, mint, await ataOf(program, mint, kp.publicKey), admin, MAX_TOTAL);
+    }
+
+    ({ pool: poolPda } = await createCappedPool(MIN_STAKE, MAX_PER_BET, MAX_TOTAL));
+  });
+
+  it("rejects a minimum stake above the per-bet cap", async () => {
+    await expectError(
+      () => createCappedPool(MAX_PER_BET + 1, MAX_PER_BET, 0),
+      "InvalidStakeLimits",
+    );
+  });
+
+  it("rejects a bet below the minimum stake", async () => {
+    await expectError(() => bet(alice, MIN_STAKE - 1), "StakeBelowMinimum");
+  });
+
+  it("rejects a bet above the per-bet cap", async () => {
+    await expectError(() => bet(alice, MAX_PER_BET + 1), "StakeAboveMaximum");
+  });
+
+  it("rejects a bet that would overfill the pool", async () => {
+    await bet(alice, MAX_PER_BET);
+    await expectError(() => bet(bob, MAX_TOTAL - MAX_PER_BET + 1), "PoolCapacityExceeded");
+
+    const pool = await program.account.pool.fetch(poolPda);
+    expect(pool.totalStaked.toNumber()).to.equal(MAX_PER_BET);
+  });
+});
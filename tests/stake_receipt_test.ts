@@ -0,0 +1,216 @@
+import * as anchor from "@anchor-lang/core";
+import { Program } from "@anchor-lang/core";
+import { SwivPrivacy } from "../target/types/swiv_privacy";
+import { PublicKey, SystemProgram, Keypair } from "@solana/web3.js";
+import { createMint, mintTo, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  SEED_PROTOCOL,
+  ataOf as ataOfMint,
+  balanceOf,
+  betOf as betOfPool,
+  createPool,
+  expectError,
+  initBet as initPoolBet,
+  receiptOf as receiptOfBet,
+  sleep,
+} from "./utils";
+
+describe("Stake Receipts", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = anchor.workspace.SwivPrivacy as Program<SwivPrivacy>;
+  const admin = (provider.wallet as anchor.Wallet).payer;
+
+  const endpoint = provider.connection.rpcEndpoint;
+  const isLocalnet = endpoint.includes("localhost") || endpoint.includes("127.0.0.1");
+
+  const [protocolPda] = PublicKey.findProgramAddressSync([SEED_PROTOCOL], program.programId);
+
+  const STAKE = 10 * 1e6;
+  const MAX_PER_BET = 20 * 1e6;
+  const TOP_UP = 4 * 1e6;
+  const OUTCOME = 75 * 1e6;
+
+  const alice = Keypair.generate();
+  const bob = Keypair.generate();
+  const carol = Keypair.generate();
+
+  let mint: PublicKey;
+  let adminAta: PublicKey;
+  let poolPda: PublicKey;
+  let vaultPda: PublicKey;
+  let endTime: number;
+
+  const ataOf = (owner: PublicKey) => ataOfMint(program, mint, owner);
+
+  const betOf = (user: PublicKey) => betOfPool(program, poolPda, user);
+
+  const receiptOf = (bet: PublicKey) => receiptOfBet(program, bet);
+
+  const initBet = (user: Keypair) =>
+    initPoolBet(program, { pool: poolPda, mint, user, amount: STAKE, requestId: "receipt_req" });
+
+  const addStake = async (user: Keypair, amount: number) =>
+    program.methods
+      .addStake(new anchor.BN(amount))
+      .accountsPartial({
+        user: user.publicKey,
+        protocol: protocolPda,
+        pool: poolPda,
+        bet: betOf(user.publicKey),
+        stakeReceipt: receiptOf(betOf(user.publicKey)),
+        poolVault: vaultPda,
+        solVault: null,
+        userTokenAccount: await ataOf(user.publicKey),
+        tokenMint: mint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([user])
+      .rpc();
+
+  before(async function () {
+    if (!isLocalnet) this.skip();
+
+    for (const kp of [alice, bob, carol]) {
+      const sig = await provider.connection.requestAirdrop(kp.publicKey, 1e9);
+      await provider.connection.confirmTransaction(sig);
+    }
+
+    // Finalization waits on the protocol's batch settle delay.
+    await program.methods
+      .updateConfig(null, null, new anchor.BN(0), null, null, null, null, null, null, null)
+      .accountsPartial({ admin: admin.publicKey, protocol: protocolPda, roles: null })
+      .rpc();
+    await program.methods
+      .executeConfigChange()
+      .accountsPartial({ executor: admin.publicKey, protocol: protocolPda })
+      .rpc();
+
+    mint = await createMint(provider.connection, admin, admin.publicKey, null, 6);
+    adminAta = await ataOf(admin.publicKey);
+    for (const kp of [alice, bob, carol]) {
+      await mintTo(provider.connection, admin, mint, await ataOf(kp.publicKey), admin, 3 * MAX_PER_BET);
+    }
+
+    ({ pool: poolPda, vault: vaultPda, end: endTime } = await createPool(program, {
+      title: "Receipt Pool",
+      mint,
+      end: Math.floor(Date.now() / 1000) + 25,
+      maxStakePerBet: MAX_PER_BET,
+    }));
+
+    await initBet(alice);
+    await initBet(bob);
+  });
+
+  it("rejects a top-up for a bet that does not exist", async () => {
+    await expectError(() => addStake(carol, TOP_UP), "BetNotInitialized");
+  });
+
+  it("caps the bet's stake plus every top-up, not each deposit alone", async () => {
+    await addStake(bob, TOP_UP);
+
+    // Each deposit fits under the cap on its own, but the running total would not.
+    await expectError(() => addStake(bob, MAX_PER_BET - STAKE - TOP_UP + 1), "StakeAboveMaximum");
+
+    const receipt = await program.account.stakeReceipt.fetch(receiptOf(betOf(bob.publicKey)));
+    expect(receipt.totalAmount.toNumber()).to.equal(TOP_UP);
+
+    const pool = await program.account.pool.fetch(poolPda);
+    expect(pool.pendingTopUps.toNumber()).to.equal(TOP_UP);
+  });
+
+  it("refunds an uncredited top-up at claim instead of sharing it out", async () => {
+    // Bob's top-up is never credited by update_bet, so it must not join the pot.
+    for (const user of [alice, bob]) {
+      await program.methods
+        .placeBet(new anchor.BN(OUTCOME), null, "receipt_req")
+        .accountsPartial({
+          user: user.publicKey,
+          protocol: protocolPda,
+          pool: poolPda,
+          bet: betOf(user.publicKey),
+        })
+        .signers([user])
+        .rpc();
+    }
+
+    const waitTime = (endTime - Math.floor(Date.now() / 1000) + 2) * 1000;
+    if (waitTime > 0) await sleep(waitTime);
+
+    await program.methods
+      .resolvePool(new anchor.BN(OUTCOME))
+      .accountsPartial({ admin: admin.publicKey, protocol: protocolPda, pool: poolPda, roles: null })
+      .rpc();
+
+    await program.methods
+      .batchCalculateWeights()
+      .accountsPartial({ admin: admin.publicKey, pool: poolPda })
+      .remainingAccounts(
+        [alice, bob].map((kp) => ({
+          pubkey: betOf(kp.publicKey),
+          isSigner: false,
+          isWritable: true,
+        })),
+      )
+      .rpc();
+
+    await program.methods
+      .finalizeWeights()
+      .accountsPartial({
+        admin: admin.publicKey,
+        protocol: protocolPda,
+        pool: poolPda,
+        poolVault: vaultPda,
+        solVault: null,
+        treasuryTokenAccount: adminAta,
+        treasuryWallet: null,
+        creatorTokenAccount: adminAta,
+        poolCreator: null,
+        tokenMint: mint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    const pool = await program.account.pool.fetch(poolPda);
+    const protocolFee = Math.floor((2 * STAKE * pool.feeBps.toNumber()) / 10000);
+    expect(pool.distributableAmount.toNumber()).to.equal(2 * STAKE - protocolFee);
+
+    const claim = async (user: Keypair, withReceipt: boolean) =>
+      program.methods
+        .claimReward()
+        .accountsPartial({
+          user: user.publicKey,
+          sponsor: admin.publicKey,
+          poolCreator: admin.publicKey,
+          pool: poolPda,
+          poolVault: vaultPda,
+          solVault: null,
+          bet: betOf(user.publicKey),
+          userTokenAccount: await ataOf(user.publicKey),
+          referralStats: null,
+          referralVault: null,
+          stakeReceipt: withReceipt ? receiptOf(betOf(user.publicKey)) : null,
+          tokenMint: mint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user, admin])
+        .rpc();
+
+    const share = Math.floor(pool.distributableAmount.toNumber() / 2);
+
+    const bobAta = await ataOf(bob.publicKey);
+    const bobBefore = await balanceOf(program, bobAta);
+    await claim(bob, true);
+    expect(await balanceOf(program, bobAta)).to.equal(bobBefore + share + TOP_UP);
+    expect(await provider.connection.getAccountInfo(receiptOf(betOf(bob.publicKey)))).to.be.null;
+
+    const aliceAta = await ataOf(alice.publicKey);
+    const aliceBefore = await balanceOf(program, aliceAta);
+    await claim(alice, false);
+    expect(await balanceOf(program, aliceAta)).to.equal(aliceBefore + pool.distributableAmount.toNumber() - share);
+    expect(await provider.connection.getAccountInfo(poolPda)).to.be.null;
+  });
+});
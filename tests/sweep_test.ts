@@ -0,0 +1,198 @@
+import * as anchor from "@anchor-lang/core";
+import { Program } from "@anchor-lang/core";
+import { SwivPrivacy } from "../target/types/swiv_privacy";
+import { PublicKey, Keypair } from "@solana/web3.js";
+import { createMint, mintTo, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  NO_DISPUTE_BONDS,
+  SEED_PROTOCOL,
+  ataOf as ataOfMint,
+  balanceOf,
+  betOf,
+  createPool,
+  expectError,
+  initBet,
+  setMinClaimWindow,
+  sleep,
+} from "./utils";
+
+describe("Unclaimed Sweep", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = anchor.workspace.SwivPrivacy as Program<SwivPrivacy>;
+  const admin = (provider.wallet as anchor.Wallet).payer;
+
+  const endpoint = provider.connection.rpcEndpoint;
+  const isLocalnet = endpoint.includes("localhost") || endpoint.includes("127.0.0.1");
+
+  const [protocolPda] = PublicKey.findProgramAddressSync([SEED_PROTOCOL], program.programId);
+
+  const STAKE = 10 * 1e6;
+  const CLAIM_WINDOW = 2;
+  let minClaimWindow: number;
+  const user = Keypair.generate();
+
+  let mint: PublicKey;
+  let poolPda: PublicKey;
+  let vaultPda: PublicKey;
+  let betPda: PublicKey;
+  let userAta: PublicKey;
+  let adminAta: PublicKey;
+  let treasuryAta: PublicKey;
+
+  before(async function () {
+    if (!isLocalnet) this.skip();
+
+    const sig = await provider.connection.requestAirdrop(user.publicKey, 1e9);
+    await provider.connection.confirmTransaction(sig);
+
+    // Let the test pools use a claim window of seconds.
+    minClaimWindow = await setMinClaimWindow(program, 0);
+
+    const protocol = await program.account.protocol.fetch(protocolPda);
+    mint = await createMint(provider.connection, admin, admin.publicKey, null, 6);
+    const ataOf = (owner: PublicKey) => ataOfMint(program, mint, owner);
+    adminAta = await ataOf(admin.publicKey);
+    treasuryAta = await ataOf(protocol.treasuryWallet);
+    userAta = await ataOf(user.publicKey);
+    await mintTo(provider.connection, admin, mint, userAta, admin, STAKE);
+
+    ({ pool: poolPda, vault: vaultPda } = await createPool(program, {
+      title: "Sweep Pool",
+      mint,
+      claimWindow: CLAIM_WINDOW,
+    }));
+    betPda = betOf(program, poolPda, user.publicKey);
+
+    await initBet(program, { pool: poolPda, mint, user, amount: STAKE, requestId: "sweep_req" });
+
+    await program.methods
+      .voidPool({ eventCancelled: {} })
+      .accountsPartial({
+        admin: admin.publicKey,
+        protocol: protocolPda,
+        pool: poolPda,
+        poolVault: vaultPda,
+        solVault: null,
+        creatorTokenAccount: adminAta,
+        poolCreator: null,
+        tokenMint: mint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        ...NO_DISPUTE_BONDS,
+      })
+      .rpc();
+  });
+
+  const sweep = () =>
+    program.methods
+      .sweepUnclaimed()
+      .accountsPartial({
+        caller: admin.publicKey,
+        protocol: protocolPda,
+        poolCreator: admin.publicKey,
+        pool: poolPda,
+        poolVault: vaultPda,
+        solVault: null,
+        treasuryTokenAccount: treasuryAta,
+        treasuryWallet: null,
+        rolloverPool: null,
+        rolloverVault: null,
+        tokenMint: mint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+  after(async () => {
+    if (!isLocalnet) return;
+    await setMinClaimWindow(program, minClaimWindow);
+  });
+
+  it("opens the claim window when the pool is voided", async () => {
+    const pool = await program.account.pool.fetch(poolPda);
+    expect(pool.claimDeadline.toNumber()).to.be.greaterThan(0);
+  });
+
+  it("rejects a sweep before the deadline", async () => {
+    await expectError(() => sweep(), "ClaimDeadlineNotReached");
+  });
+
+  it("rejects claims after the deadline", async () => {
+    await sleep((CLAIM_WINDOW + 2) * 1000);
+
+    await expectError(
+      () =>
+        program.methods
+          .claimReward()
+          .accountsPartial({
+            user: user.publicKey,
+            sponsor: admin.publicKey,
+            poolCreator: admin.publicKey,
+            pool: poolPda,
+            poolVault: vaultPda,
+            solVault: null,
+            bet: betPda,
+            userTokenAccount: userAta,
+            referralStats: null,
+            referralVault: null,
+            stakeReceipt: null,
+            tokenMint: mint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([user, admin])
+          .rpc(),
+      "ClaimDeadlinePassed",
+    );
+  });
+
+  const closeOrphanedBet = () =>
+    program.methods
+      .closeOrphanedBet()
+      .accountsPartial({
+        caller: admin.publicKey,
+        sponsor: admin.publicKey,
+        user: user.publicKey,
+        bet: betPda,
+        pool: poolPda,
+        stakeReceipt: null,
+      })
+      .rpc();
+
+  it("keeps unclaimed bets open while their pool is", async () => {
+    await expectError(() => closeOrphanedBet(), "PoolStillOpen");
+  });
+
+  it("sweeps the vault to the treasury and closes the pool", async () => {
+    const before = await balanceOf(program, treasuryAta);
+
+    await sweep();
+
+    const after = await balanceOf(program, treasuryAta);
+    expect(after - before).to.equal(STAKE);
+    expect(await provider.connection.getAccountInfo(vaultPda)).to.be.null;
+    expect(await provider.connection.getAccountInfo(poolPda)).to.be.null;
+  });
+
+  it("returns the rent of a bet orphaned by the sweep to its sponsor", async () => {
+    const rent = (await provider.connection.getAccountInfo(betPda))!.lamports;
+    const sponsorBefore = await provider.connection.getBalance(admin.publicKey);
+
+    await closeOrphanedBet();
+
+    expect(await provider.connection.getAccountInfo(betPda)).to.be.null;
+    // The admin sponsored the bet and also pays the 5000-lamport transaction fee.
+    expect(await provider.connection.getBalance(admin.publicKey)).to.equal(sponsorBefore + rent - 5000);
+  });
+
+  it("rejects a claim window below the protocol minimum", async () => {
+    await setMinClaimWindow(program, CLAIM_WINDOW + 1);
+    try {
+      await expectError(
+        () => createPool(program, { title: "Short Claim Pool", mint, claimWindow: CLAIM_WINDOW }),
+        "InvalidClaimWindow",
+      );
+    } finally {
+      await setMinClaimWindow(program, 0);
+    }
+  });
+});
@@ -0,0 +1,320 @@
+import * as anchor from "@anchor-lang/core";
+import { Program } from "@anchor-lang/core";
+import { SwivPrivacy } from "../target/types/swiv_privacy";
+import {
+  PublicKey,
+  SystemProgram,
+  Keypair,
+  Transaction,
+  sendAndConfirmTransaction,
+} from "@solana/web3.js";
+import {
+  ExtensionType,
+  TOKEN_2022_PROGRAM_ID,
+  createInitializeMintInstruction,
+  createInitializePermanentDelegateInstruction,
+  getMintLen,
+  mintTo,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  NO_DISPUTE_BONDS,
+  SEED_PROTOCOL,
+  ataOf as ataOfMint,
+  balanceOf,
+  betOf,
+  createPool,
+  createTransferFeeMint,
+  expectError,
+  initBet,
+  pdaOf,
+  setMinClaimWindow,
+  sleep,
+  withheldFeesOf,
+} from "./utils";
+
+describe("Token-2022 Stake Mints", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = anchor.workspace.SwivPrivacy as Program<SwivPrivacy>;
+  const admin = (provider.wallet as anchor.Wallet).payer;
+
+  const endpoint = provider.connection.rpcEndpoint;
+  const isLocalnet = endpoint.includes("localhost") || endpoint.includes("127.0.0.1");
+
+  const protocolPda = pdaOf(program, [SEED_PROTOCOL]);
+
+  const STAKE = 10 * 1e6;
+  const TRANSFER_FEE_BPS = 100;
+  const OUTCOME = 75 * 1e6;
+  const CLAIM_WINDOW = 2;
+  let minClaimWindow: number;
+  const alice = Keypair.generate();
+  const bob = Keypair.generate();
+
+  let mint: PublicKey;
+  let poolPda: PublicKey;
+  let vaultPda: PublicKey;
+  let endTime: number;
+  let treasuryAta: PublicKey;
+
+  const afterFee = (amount: number) => amount - Math.ceil((amount * TRANSFER_FEE_BPS) / 10000);
+  const received = afterFee(STAKE);
+
+  const ataOf = (owner: PublicKey) => ataOfMint(program, mint, owner, TOKEN_2022_PROGRAM_ID);
+
+  const bet = (pool: PublicKey, user: Keypair) =>
+    initBet(program, {
+      pool,
+      mint,
+      user,
+      amount: STAKE,
+      requestId: "t22_req",
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+    });
+
+  const claim = async (user: Keypair) =>
+    program.methods
+      .claimReward()
+      .accountsPartial({
+        user: user.publicKey,
+        sponsor: admin.publicKey,
+        poolCreator: admin.publicKey,
+        pool: poolPda,
+        poolVault: vaultPda,
+        solVault: null,
+        bet: betOf(program, poolPda, user.publicKey),
+        userTokenAccount: await ataOf(user.publicKey),
+        referralStats: null,
+        referralVault: null,
+        stakeReceipt: null,
+        tokenMint: mint,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .signers([user, admin])
+      .rpc();
+
+  before(async function () {
+    if (!isLocalnet) this.skip();
+
+    for (const kp of [alice, bob]) {
+      const sig = await provider.connection.requestAirdrop(kp.publicKey, 1e9);
+      await provider.connection.confirmTransaction(sig);
+    }
+
+    // Finalization waits on the protocol's batch settle delay.
+    await program.methods
+      .updateConfig(null, null, new anchor.BN(0), null, null, null, null, null, null, null)
+      .accountsPartial({ admin: admin.publicKey, protocol: protocolPda, roles: null })
+      .rpc();
+    await program.methods
+      .executeConfigChange()
+      .accountsPartial({ executor: admin.publicKey, protocol: protocolPda })
+      .rpc();
+
+    // Let the sweep test pool use a claim window of seconds.
+    minClaimWindow = await setMinClaimWindow(program, 0);
+
+    // 1% transfer fee, uncapped for the amounts used here.
+    mint = await createTransferFeeMint(program, TRANSFER_FEE_BPS, STAKE);
+
+    for (const kp of [alice, bob]) {
+      await mintTo(
+        provider.connection,
+        admin,
+        mint,
+        await ataOf(kp.publicKey),
+        admin,
+        2 * STAKE,
+        [],
+        undefined,
+        TOKEN_2022_PROGRAM_ID,
+      );
+    }
+
+    const { treasuryWallet } = await program.account.protocol.fetch(protocolPda);
+    treasuryAta = await ataOf(treasuryWallet);
+
+    ({ pool: poolPda, vault: vaultPda, end: endTime } = await createPool(program, {
+      title: "Token-2022 Pool",
+      mint,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+      end: Math.floor(Date.now() / 1000) + 25,
+    }));
+  });
+
+  after(async () => {
+    if (!isLocalnet) return;
+    await setMinClaimWindow(program, minClaimWindow);
+  });
+
+  it("stakes only what reaches the vault after the transfer fee", async () => {
+    await bet(poolPda, alice);
+    await bet(poolPda, bob);
+
+    const stake = await program.account.bet.fetch(betOf(program, poolPda, alice.publicKey));
+    expect(stake.stake.toNumber()).to.equal(received);
+
+    const pool = await program.account.pool.fetch(poolPda);
+    expect(pool.totalStaked.toNumber()).to.equal(2 * received);
+
+    expect(await balanceOf(program, vaultPda)).to.equal(2 * received);
+  });
+
+  it("rejects a mint with a permanent delegate", async () => {
+    // The delegate could drain the pool vault at will.
+    const mintKeypair = Keypair.generate();
+    const mintLen = getMintLen([ExtensionType.PermanentDelegate]);
+    await sendAndConfirmTransaction(
+      provider.connection,
+      new Transaction().add(
+        SystemProgram.createAccount({
+          fromPubkey: admin.publicKey,
+          newAccountPubkey: mintKeypair.publicKey,
+          space: mintLen,
+          lamports: await provider.connection.getMinimumBalanceForRentExemption(mintLen),
+          programId: TOKEN_2022_PROGRAM_ID,
+        }),
+        createInitializePermanentDelegateInstruction(
+          mintKeypair.publicKey,
+          admin.publicKey,
+          TOKEN_2022_PROGRAM_ID,
+        ),
+        createInitializeMintInstruction(mintKeypair.publicKey, 6, admin.publicKey, null, TOKEN_2022_PROGRAM_ID),
+      ),
+      [admin, mintKeypair],
+    );
+
+    await expectError(
+      () => createPool(program, { mint: mintKeypair.publicKey, tokenProgram: TOKEN_2022_PROGRAM_ID }),
+      "UnsupportedMintExtension",
+    );
+  });
+
+  it("finalizes, pays out and closes a vault holding withheld fees", async () => {
+    for (const user of [alice, bob]) {
+      await program.methods
+        .placeBet(new anchor.BN(OUTCOME), null, "t22_req")
+        .accountsPartial({
+          user: user.publicKey,
+          protocol: protocolPda,
+          pool: poolPda,
+          bet: betOf(program, poolPda, user.publicKey),
+        })
+        .signers([user])
+        .rpc();
+    }
+
+    const waitTime = (endTime - Math.floor(Date.now() / 1000) + 2) * 1000;
+    if (waitTime > 0) await sleep(waitTime);
+
+    await program.methods
+      .resolvePool(new anchor.BN(OUTCOME))
+      .accountsPartial({ admin: admin.publicKey, protocol: protocolPda, pool: poolPda, roles: null })
+      .rpc();
+
+    await program.methods
+      .batchCalculateWeights()
+      .accountsPartial({ admin: admin.publicKey, pool: poolPda })
+      .remainingAccounts(
+        [alice, bob].map((kp) => ({
+          pubkey: betOf(program, poolPda, kp.publicKey),
+          isSigner: false,
+          isWritable: true,
+        })),
+      )
+      .rpc();
+
+    await program.methods
+      .finalizeWeights()
+      .accountsPartial({
+        admin: admin.publicKey,
+        protocol: protocolPda,
+        pool: poolPda,
+        poolVault: vaultPda,
+        solVault: null,
+        treasuryTokenAccount: treasuryAta,
+        treasuryWallet: null,
+        creatorTokenAccount: await ataOf(admin.publicKey),
+        poolCreator: null,
+        tokenMint: mint,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .rpc();
+
+    const pool = await program.account.pool.fetch(poolPda);
+    const distributable = pool.distributableAmount.toNumber();
+    const protocolFee = Math.floor((2 * received * pool.feeBps.toNumber()) / 10000);
+    expect(distributable).to.equal(2 * received - protocolFee);
+    expect(await balanceOf(program, vaultPda)).to.equal(distributable);
+
+    const share = Math.floor(distributable / 2);
+    const aliceAta = await ataOf(alice.publicKey);
+    const aliceBefore = await balanceOf(program, aliceAta);
+    await claim(alice);
+    expect(await balanceOf(program, aliceAta)).to.equal(aliceBefore + afterFee(share));
+    expect(await balanceOf(program, vaultPda)).to.equal(distributable - share);
+
+    // The deposits left fees withheld on the vault; the last claim harvests them to the mint.
+    const bobAta = await ataOf(bob.publicKey);
+    const bobBefore = await balanceOf(program, bobAta);
+    await claim(bob);
+    expect(await balanceOf(program, bobAta)).to.equal(bobBefore + afterFee(distributable - share));
+    expect(await provider.connection.getAccountInfo(vaultPda)).to.be.null;
+    expect(await provider.connection.getAccountInfo(poolPda)).to.be.null;
+    expect(await withheldFeesOf(program, mint)).to.equal(2 * (STAKE - received));
+  });
+
+  it("sweeps and closes a vault holding withheld fees", async () => {
+    const { pool, vault } = await createPool(program, {
+      title: "Token-2022 Sweep Pool",
+      mint,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+      claimWindow: CLAIM_WINDOW,
+    });
+    await bet(pool, alice);
+    expect(await balanceOf(program, vault)).to.equal(received);
+
+    await program.methods
+      .voidPool({ eventCancelled: {} })
+      .accountsPartial({
+        admin: admin.publicKey,
+        protocol: protocolPda,
+        pool,
+        poolVault: vault,
+        solVault: null,
+        creatorTokenAccount: await ataOf(admin.publicKey),
+        poolCreator: null,
+        tokenMint: mint,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        ...NO_DISPUTE_BONDS,
+      })
+      .rpc();
+    await sleep((CLAIM_WINDOW + 2) * 1000);
+
+    const withheldBefore = await withheldFeesOf(program, mint);
+    const treasuryBefore = await balanceOf(program, treasuryAta);
+    await program.methods
+      .sweepUnclaimed()
+      .accountsPartial({
+        caller: admin.publicKey,
+        protocol: protocolPda,
+        poolCreator: admin.publicKey,
+        pool,
+        poolVault: vault,
+        solVault: null,
+        treasuryTokenAccount: treasuryAta,
+        treasuryWallet: null,
+        rolloverPool: null,
+        rolloverVault: null,
+        tokenMint: mint,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .rpc();
+
+    expect(await balanceOf(program, treasuryAta)).to.equal(treasuryBefore + afterFee(received));
+    expect(await provider.connection.getAccountInfo(vault)).to.be.null;
+    expect(await provider.connection.getAccountInfo(pool)).to.be.null;
+    expect(await withheldFeesOf(program, mint)).to.equal(withheldBefore + STAKE - received);
+  });
+});
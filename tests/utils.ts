@@ -1,10 +1,35 @@
-import { PublicKey } from "@solana/web3.js";
+import {
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  Transaction,
+  sendAndConfirmTransaction,
+} from "@solana/web3.js";
 import * as anchor from "@anchor-lang/core";
+import { Program } from "@anchor-lang/core";
+import {
+  ExtensionType,
+  TOKEN_2022_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  createInitializeMintInstruction,
+  createInitializeTransferFeeConfigInstruction,
+  getMint,
+  getMintLen,
+  getOrCreateAssociatedTokenAccount,
+  getTransferFeeConfig,
+} from "@solana/spl-token";
+import { SwivPrivacy } from "../target/types/swiv_privacy";
 
 export const SEED_BET = Buffer.from("bet");
 export const SEED_POOL = Buffer.from("pool");
 export const SEED_POOL_VAULT = Buffer.from("pool_vault");
 export const SEED_PROTOCOL = Buffer.from("protocol_v1");
+export const SEED_CREATOR_BOND = Buffer.from("creator_bond");
+export const SEED_REFERRAL = Buffer.from("referral");
+export const SEED_REFERRAL_VAULT = Buffer.from("referral_vault");
+export const SEED_STAKE_RECEIPT = Buffer.from("stake_receipt");
+export const SEED_SOL_VAULT = Buffer.from("sol_vault");
+export const SEED_ROLES = Buffer.from("roles");
 
 // Dynamically check if we are on localnet
 let isLocalnet = true;
@@ -21,6 +46,253 @@ export const TEE_VALIDATOR = isLocalnet
 
 export const sleep = (ms: number) => new Promise((resolve) => setTimeout(resolve, ms));
 
+export const expectError = async (fn: () => Promise<unknown>, code: string) => {
+  try {
+    await fn();
+    throw new Error(`Should have failed with ${code}`);
+  } catch (e: any) {
+    if (!e.message.includes(code)) throw e;
+  }
+};
+
+const payerOf = (program: Program<SwivPrivacy>) =>
+  ((program.provider as anchor.AnchorProvider).wallet as anchor.Wallet).payer;
+
+export const pdaOf = (program: Program<SwivPrivacy>, seeds: Buffer[]) =>
+  PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+
+export const betOf = (program: Program<SwivPrivacy>, pool: PublicKey, user: PublicKey) =>
+  pdaOf(program, [SEED_BET, pool.toBuffer(), user.toBuffer()]);
+
+export const receiptOf = (program: Program<SwivPrivacy>, bet: PublicKey) =>
+  pdaOf(program, [SEED_STAKE_RECEIPT, bet.toBuffer()]);
+
+/** The owner's associated token account for `mint`, created on first use. */
+export const ataOf = async (
+  program: Program<SwivPrivacy>,
+  mint: PublicKey,
+  owner: PublicKey,
+  tokenProgram: PublicKey = TOKEN_PROGRAM_ID,
+) =>
+  (
+    await getOrCreateAssociatedTokenAccount(
+      program.provider.connection,
+      payerOf(program),
+      mint,
+      owner,
+      false,
+      undefined,
+      undefined,
+      tokenProgram,
+    )
+  ).address;
+
+export const balanceOf = async (program: Program<SwivPrivacy>, account: PublicKey) =>
+  Number((await program.provider.connection.getTokenAccountBalance(account)).value.amount);
+
+/** A 6-decimal Token-2022 mint charging `feeBps` on every transfer, up to `maxFee`. */
+export const createTransferFeeMint = async (
+  program: Program<SwivPrivacy>,
+  feeBps: number,
+  maxFee: number,
+) => {
+  const admin = payerOf(program);
+  const mintKeypair = Keypair.generate();
+  const mint = mintKeypair.publicKey;
+  const mintLen = getMintLen([ExtensionType.TransferFeeConfig]);
+  const lamports = await program.provider.connection.getMinimumBalanceForRentExemption(mintLen);
+  await sendAndConfirmTransaction(
+    program.provider.connection,
+    new Transaction().add(
+      SystemProgram.createAccount({
+        fromPubkey: admin.publicKey,
+        newAccountPubkey: mint,
+        space: mintLen,
+        lamports,
+        programId: TOKEN_2022_PROGRAM_ID,
+      }),
+      createInitializeTransferFeeConfigInstruction(
+        mint,
+        admin.publicKey,
+        admin.publicKey,
+        feeBps,
+        BigInt(maxFee),
+        TOKEN_2022_PROGRAM_ID,
+      ),
+      createInitializeMintInstruction(mint, 6, admin.publicKey, null, TOKEN_2022_PROGRAM_ID),
+    ),
+    [admin, mintKeypair],
+  );
+  return mint;
+};
+
+/** Transfer fees harvested to a Token-2022 `mint` and not yet withdrawn. */
+export const withheldFeesOf = async (program: Program<SwivPrivacy>, mint: PublicKey) => {
+  const info = await getMint(program.provider.connection, mint, undefined, TOKEN_2022_PROGRAM_ID);
+  return Number(getTransferFeeConfig(info)!.withheldAmount);
+};
+
+/** Sets `protocol.min_claim_window` as the admin; returns the previous value. */
+export const setMinClaimWindow = async (program: Program<SwivPrivacy>, seconds: number) => {
+  const protocol = pdaOf(program, [SEED_PROTOCOL]);
+  const { minClaimWindow } = await program.account.protocol.fetch(protocol);
+  await program.methods
+    .updateConfig(null, null, null, null, null, null, null, null, null, new anchor.BN(seconds))
+    .accountsPartial({ admin: payerOf(program).publicKey, protocol, roles: null })
+    .rpc();
+  return minClaimWindow.toNumber();
+};
+
+export const NO_CONVICTION = {
+  bonusBps: new anchor.BN(0),
+  decayBps: new anchor.BN(0),
+  floorBps: new anchor.BN(0),
+};
+export const NO_TIME_BONUS = { curve: { none: {} }, maxBonusBps: new anchor.BN(0), resetOnUpdate: false };
+/** The bond accounts `void_pool` and `emergency_refund` only need for a timed-out dispute. */
+export const NO_DISPUTE_BONDS = {
+  proposal: null,
+  bondVault: null,
+  proposer: null,
+  proposerTokenAccount: null,
+  disputerTokenAccount: null,
+};
+export const PRICE_OUTCOME = {
+  kind: { numeric: {} },
+  labels: [] as string[],
+  exponent: -6,
+  accuracyScale: new anchor.BN(0),
+};
+
+/** `create_pool` arguments and accounts; anything left out gets a plain token-pool default. */
+export type PoolOptions = {
+  title?: string;
+  start?: number;
+  end?: number;
+  maxAccuracyBuffer?: number;
+  conviction?: typeof NO_CONVICTION;
+  oracle?: { feed: PublicKey; exponent: number; maxStaleness: anchor.BN } | null;
+  scoringRule?: object;
+  timeBonus?: object;
+  outcome?: object;
+  creatorFeeBps?: number;
+  subsidy?: number;
+  minStake?: number;
+  maxStakePerBet?: number;
+  maxPoolTotal?: number;
+  exitPenaltyBps?: number;
+  claimWindow?: number;
+  /** Native SOL pool; `mint` is ignored. */
+  native?: boolean;
+  mint?: PublicKey;
+  tokenProgram?: PublicKey;
+  creator?: Keypair;
+  roles?: PublicKey | null;
+  creatorTokenAccount?: PublicKey;
+  resolverCommittee?: PublicKey | null;
+  priceFeed?: PublicKey | null;
+  /** Overrides for any other `create_pool` account. */
+  accounts?: Record<string, PublicKey | null>;
+};
+
+/** Creates the protocol's next pool, by default ending in 10 minutes. */
+export const createPool = async (program: Program<SwivPrivacy>, options: PoolOptions = {}) => {
+  const admin = payerOf(program);
+  const creator = options.creator ?? admin;
+  const protocol = pdaOf(program, [SEED_PROTOCOL]);
+  const { totalPools } = await program.account.protocol.fetch(protocol);
+  const pool = pdaOf(program, [SEED_POOL, creator.publicKey.toBuffer(), totalPools.toBuffer("le", 8)]);
+  const native = options.native ?? false;
+  const vault = pdaOf(program, [native ? SEED_SOL_VAULT : SEED_POOL_VAULT, pool.toBuffer()]);
+  const tokenProgram = options.tokenProgram ?? TOKEN_PROGRAM_ID;
+
+  const now = Math.floor(Date.now() / 1000);
+  const start = options.start ?? now - 10;
+  const end = options.end ?? now + 600;
+
+  const creatorTokenAccount = native
+    ? null
+    : options.creatorTokenAccount ??
+      (await ataOf(program, options.mint!, creator.publicKey, tokenProgram));
+
+  const builder = program.methods
+    .createPool(
+      options.title ?? "Test Pool",
+      new anchor.BN(start),
+      new anchor.BN(end),
+      new anchor.BN(options.maxAccuracyBuffer ?? 5 * 1e6),
+      options.conviction ?? NO_CONVICTION,
+      options.oracle ?? null,
+      (options.scoringRule ?? { hyperbolic: {} }) as any,
+      (options.timeBonus ?? NO_TIME_BONUS) as any,
+      (options.outcome ?? PRICE_OUTCOME) as any,
+      new anchor.BN(options.creatorFeeBps ?? 0),
+      new anchor.BN(options.subsidy ?? 0),
+      {
+        minStake: new anchor.BN(options.minStake ?? 0),
+        maxStakePerBet: new anchor.BN(options.maxStakePerBet ?? 0),
+        maxPoolTotal: new anchor.BN(options.maxPoolTotal ?? 0),
+      },
+      new anchor.BN(options.exitPenaltyBps ?? 0),
+      new anchor.BN(options.claimWindow ?? 0),
+      native ? { native: {} } : { token: {} },
+    )
+    .accountsPartial({
+      protocol,
+      pool,
+      poolVault: native ? null : vault,
+      solVault: native ? vault : null,
+      tokenMint: native ? null : options.mint!,
+      resolverCommittee: options.resolverCommittee ?? null,
+      priceFeed: options.priceFeed ?? null,
+      createdBy: creator.publicKey,
+      roles: options.roles ?? null,
+      createdByTokenAccount: creatorTokenAccount,
+      tokenProgram: native ? null : tokenProgram,
+      systemProgram: SystemProgram.programId,
+      rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      ...options.accounts,
+    });
+  await (creator === admin ? builder : builder.signers([creator])).rpc();
+
+  return { pool, vault, start, end };
+};
+
+/** Opens a bet on a token pool, with the provider wallet sponsoring the rent. */
+export const initBet = async (
+  program: Program<SwivPrivacy>,
+  options: {
+    pool: PublicKey;
+    mint: PublicKey;
+    user: Keypair;
+    amount: number;
+    requestId?: string;
+    referrer?: PublicKey | null;
+    tokenProgram?: PublicKey;
+  },
+) => {
+  const admin = payerOf(program);
+  const tokenProgram = options.tokenProgram ?? TOKEN_PROGRAM_ID;
+  return program.methods
+    .initBet(new anchor.BN(options.amount), options.requestId ?? "test_req", options.referrer ?? null)
+    .accountsPartial({
+      user: options.user.publicKey,
+      sponsor: admin.publicKey,
+      roles: null,
+      protocol: pdaOf(program, [SEED_PROTOCOL]),
+      pool: options.pool,
+      poolVault: pdaOf(program, [SEED_POOL_VAULT, options.pool.toBuffer()]),
+      solVault: null,
+      userTokenAccount: await ataOf(program, options.mint, options.user.publicKey, tokenProgram),
+      bet: betOf(program, options.pool, options.user.publicKey),
+      tokenMint: options.mint,
+      tokenProgram,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([options.user, admin])
+    .rpc();
+};
+
 export { 
   permissionPdaFromAccount, 
   verifyTeeRpcIntegrity, 
@@ -34,4 +306,4 @@ export {
   delegationRecordPdaFromDelegatedAccount, 
   delegationMetadataPdaFromDelegatedAccount, 
   delegateBufferPdaFromDelegatedAccountAndOwnerProgram 
-} from "@magicblock-labs/ephemeral-rollups-sdk";
\ No newline at end of file
+} from "@magicblock-labs/ephemeral-rollups-sdk";
@@ -0,0 +1,184 @@
+import * as anchor from "@anchor-lang/core";
+import { Program } from "@anchor-lang/core";
+import { SwivPrivacy } from "../target/types/swiv_privacy";
+import { PublicKey, Keypair } from "@solana/web3.js";
+import { createMint, mintTo, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  NO_DISPUTE_BONDS,
+  SEED_PROTOCOL,
+  ataOf,
+  balanceOf,
+  betOf,
+  createPool,
+  expectError,
+  initBet,
+  receiptOf,
+} from "./utils";
+
+describe("Void Pool", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = anchor.workspace.SwivPrivacy as Program<SwivPrivacy>;
+  const admin = (provider.wallet as anchor.Wallet).payer;
+
+  const endpoint = provider.connection.rpcEndpoint;
+  const isLocalnet = endpoint.includes("localhost") || endpoint.includes("127.0.0.1");
+
+  const [protocolPda] = PublicKey.findProgramAddressSync([SEED_PROTOCOL], program.programId);
+
+  const STAKE = 10 * 1e6;
+  const SUBSIDY = 5 * 1e6;
+  const EXIT_PENALTY_BPS = 1000;
+  const user = Keypair.generate();
+
+  let mint: PublicKey;
+  let poolPda: PublicKey;
+  let vaultPda: PublicKey;
+  let userAta: PublicKey;
+  let adminAta: PublicKey;
+
+  const voidPool = (pool: PublicKey, vault: PublicKey) =>
+    program.methods
+      .voidPool({ dataUnavailable: {} })
+      .accountsPartial({
+        admin: admin.publicKey,
+        protocol: protocolPda,
+        pool,
+        poolVault: vault,
+        solVault: null,
+        creatorTokenAccount: adminAta,
+        poolCreator: null,
+        tokenMint: mint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        ...NO_DISPUTE_BONDS,
+      })
+      .rpc();
+
+  const claim = (pool: PublicKey, vault: PublicKey) =>
+    program.methods
+      .claimReward()
+      .accountsPartial({
+        user: user.publicKey,
+        sponsor: admin.publicKey,
+        poolCreator: admin.publicKey,
+        pool,
+        poolVault: vault,
+        solVault: null,
+        bet: betOf(program, pool, user.publicKey),
+        userTokenAccount: userAta,
+        referralStats: null,
+        referralVault: null,
+        stakeReceipt: null,
+        tokenMint: mint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user, admin])
+      .rpc();
+
+  before(async function () {
+    if (!isLocalnet) this.skip();
+
+    const sig = await provider.connection.requestAirdrop(user.publicKey, 1e9);
+    await provider.connection.confirmTransaction(sig);
+
+    mint = await createMint(provider.connection, admin, admin.publicKey, null, 6);
+    adminAta = await ataOf(program, mint, admin.publicKey);
+    await mintTo(provider.connection, admin, mint, adminAta, admin, SUBSIDY);
+    userAta = await ataOf(program, mint, user.publicKey);
+    await mintTo(provider.connection, admin, mint, userAta, admin, STAKE);
+
+    ({ pool: poolPda, vault: vaultPda } = await createPool(program, {
+      title: "Void Pool",
+      mint,
+      subsidy: SUBSIDY,
+    }));
+
+    await initBet(program, { pool: poolPda, mint, user, amount: STAKE, requestId: "void_req" });
+  });
+
+  it("rejects voiding without a reason", async () => {
+    await expectError(
+      () =>
+        program.methods
+          .voidPool({ none: {} })
+          .accountsPartial({
+            admin: admin.publicKey,
+            protocol: protocolPda,
+            pool: poolPda,
+            poolVault: vaultPda,
+            solVault: null,
+            creatorTokenAccount: adminAta,
+            poolCreator: null,
+            tokenMint: mint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            ...NO_DISPUTE_BONDS,
+          })
+          .rpc(),
+      "PoolNotVoidable",
+    );
+  });
+
+  it("voids the pool and records the reason", async () => {
+    await voidPool(poolPda, vaultPda);
+
+    const pool = await program.account.pool.fetch(poolPda);
+    expect(pool.status).to.deep.equal({ voided: {} });
+    expect(pool.voidReason).to.deep.equal({ dataUnavailable: {} });
+  });
+
+  it("returns the creator subsidy on void", async () => {
+    const pool = await program.account.pool.fetch(poolPda);
+    expect(pool.subsidyAmount.toNumber()).to.equal(0);
+
+    expect(await balanceOf(program, adminAta)).to.equal(SUBSIDY);
+    expect(await balanceOf(program, vaultPda)).to.equal(STAKE);
+  });
+
+  it("refunds the exact stake without weights or finalization", async () => {
+    await claim(poolPda, vaultPda);
+
+    expect(await balanceOf(program, userAta)).to.equal(STAKE);
+  });
+
+  it("gives the last claimant the exit penalties left in the vault", async () => {
+    const { pool, vault } = await createPool(program, {
+      title: "Void After Withdraw",
+      mint,
+      exitPenaltyBps: EXIT_PENALTY_BPS,
+    });
+    const bet = betOf(program, pool, user.publicKey);
+    await initBet(program, { pool, mint, user, amount: STAKE, requestId: "void_req" });
+
+    const withdrawn = 4 * 1e6;
+    await program.methods
+      .withdrawBet(new anchor.BN(withdrawn))
+      .accountsPartial({
+        user: user.publicKey,
+        protocol: protocolPda,
+        sponsor: admin.publicKey,
+        pool,
+        poolVault: vault,
+        solVault: null,
+        bet,
+        stakeReceipt: receiptOf(program, bet),
+        userTokenAccount: userAta,
+        tokenMint: mint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+    // The penalty stays in the vault, so it holds more than the remaining stake.
+    const penalty = (withdrawn * EXIT_PENALTY_BPS) / 10000;
+    expect((await program.account.bet.fetch(bet)).stake.toNumber()).to.equal(STAKE - withdrawn);
+    expect(await balanceOf(program, vault)).to.equal(STAKE - withdrawn + penalty);
+
+    await voidPool(pool, vault);
+    await claim(pool, vault);
+
+    expect(await balanceOf(program, userAta)).to.equal(STAKE);
+    expect(await provider.connection.getAccountInfo(vault)).to.be.null;
+    expect(await provider.connection.getAccountInfo(pool)).to.be.null;
+  });
+});
@@ -0,0 +1,153 @@
+import * as anchor from "@anchor-lang/core";
+import { Program } from "@anchor-lang/core";
+import { SwivPrivacy } from "../target/types/swiv_privacy";
+import { PublicKey, SystemProgram, Keypair } from "@solana/web3.js";
+import { createMint, mintTo, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  SEED_PROTOCOL,
+  ataOf as ataOfMint,
+  balanceOf,
+  betOf as betOfPool,
+  createPool,
+  expectError,
+  initBet,
+  receiptOf as receiptOfBet,
+} from "./utils";
+
+describe("Withdraw Bet", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = anchor.workspace.SwivPrivacy as Program<SwivPrivacy>;
+  const admin = (provider.wallet as anchor.Wallet).payer;
+
+  const endpoint = provider.connection.rpcEndpoint;
+  const isLocalnet = endpoint.includes("localhost") || endpoint.includes("127.0.0.1");
+
+  const [protocolPda] = PublicKey.findProgramAddressSync([SEED_PROTOCOL], program.programId);
+
+  const STAKE = 10 * 1e6;
+  const EXIT_PENALTY_BPS = 1000;
+  const TOP_UP = 2 * 1e6;
+
+  const alice = Keypair.generate();
+  const bob = Keypair.generate();
+
+  let mint: PublicKey;
+  let poolPda: PublicKey;
+  let vaultPda: PublicKey;
+
+  const ataOf = (owner: PublicKey) => ataOfMint(program, mint, owner);
+
+  const betOf = (user: PublicKey) => betOfPool(program, poolPda, user);
+
+  const receiptOf = (bet: PublicKey) => receiptOfBet(program, bet);
+
+  const withdraw = async (user: Keypair, amount: number) =>
+    program.methods
+      .withdrawBet(new anchor.BN(amount))
+      .accountsPartial({
+        user: user.publicKey,
+        protocol: protocolPda,
+        sponsor: admin.publicKey,
+        pool: poolPda,
+        poolVault: vaultPda,
+        solVault: null,
+        bet: betOf(user.publicKey),
+        stakeReceipt: receiptOf(betOf(user.publicKey)),
+        userTokenAccount: await ataOf(user.publicKey),
+        tokenMint: mint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+  before(async function () {
+    if (!isLocalnet) this.skip();
+
+    for (const kp of [alice, bob]) {
+      const sig = await provider.connection.requestAirdrop(kp.publicKey, 1e9);
+      await provider.connection.confirmTransaction(sig);
+    }
+
+    mint = await createMint(provider.connection, admin, admin.publicKey, null, 6);
+    for (const kp of [alice, bob]) {
+      await mintTo(provider.connection, admin, mint, await ataOf(kp.publicKey), admin, STAKE + TOP_UP);
+    }
+
+    ({ pool: poolPda, vault: vaultPda } = await createPool(program, {
+      title: "Withdraw Pool",
+      mint,
+      exitPenaltyBps: EXIT_PENALTY_BPS,
+    }));
+
+    for (const user of [alice, bob]) {
+      await initBet(program, { pool: poolPda, mint, user, amount: STAKE, requestId: "withdraw_req" });
+    }
+  });
+
+  it("rejects withdrawing more than the stake", async () => {
+    await expectError(() => withdraw(bob, STAKE + 1), "InvalidWithdrawAmount");
+  });
+
+  it("reduces a bet and keeps the penalty in the pool", async () => {
+    const amount = 4 * 1e6;
+    await withdraw(alice, amount);
+
+    const bet = await program.account.bet.fetch(betOf(alice.publicKey));
+    expect(bet.stake.toNumber()).to.equal(STAKE - amount);
+    expect(await balanceOf(program, await ataOf(alice.publicKey))).to.equal(TOP_UP + amount * 0.9);
+
+    const pool = await program.account.pool.fetch(poolPda);
+    expect(pool.totalParticipants.toNumber()).to.equal(2);
+    expect(await balanceOf(program, vaultPda)).to.equal(2 * STAKE - amount * 0.9);
+  });
+
+  it("closes the bet on a full exit", async () => {
+    await withdraw(alice, 6 * 1e6);
+
+    expect(await provider.connection.getAccountInfo(betOf(alice.publicKey))).to.be.null;
+    expect(await balanceOf(program, await ataOf(alice.publicKey))).to.equal(TOP_UP + STAKE * 0.9);
+
+    const pool = await program.account.pool.fetch(poolPda);
+    expect(pool.totalParticipants.toNumber()).to.equal(1);
+    expect(pool.totalStaked.toNumber()).to.equal(STAKE + STAKE * 0.1);
+  });
+
+  it("refunds an uncredited top-up and closes the receipt on a full exit", async () => {
+    const bet = betOf(bob.publicKey);
+    const receipt = receiptOf(bet);
+    const bobAta = await ataOf(bob.publicKey);
+
+    await program.methods
+      .addStake(new anchor.BN(TOP_UP))
+      .accountsPartial({
+        user: bob.publicKey,
+        protocol: protocolPda,
+        pool: poolPda,
+        bet,
+        stakeReceipt: receipt,
+        poolVault: vaultPda,
+        solVault: null,
+        userTokenAccount: bobAta,
+        tokenMint: mint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([bob])
+      .rpc();
+    expect((await program.account.pool.fetch(poolPda)).pendingTopUps.toNumber()).to.equal(TOP_UP);
+
+    // The top-up was never credited to the stake, so it comes back without a penalty.
+    await withdraw(bob, STAKE);
+
+    expect(await balanceOf(program, bobAta)).to.equal(STAKE * 0.9 + TOP_UP);
+    expect(await provider.connection.getAccountInfo(bet)).to.be.null;
+    expect(await provider.connection.getAccountInfo(receipt)).to.be.null;
+
+    const pool = await program.account.pool.fetch(poolPda);
+    expect(pool.totalParticipants.toNumber()).to.equal(0);
+    expect(pool.pendingTopUps.toNumber()).to.equal(0);
+    expect(pool.totalStaked.toNumber()).to.equal(2 * STAKE * 0.1);
+  });
+});